@@ -1,8 +1,9 @@
-use std::cmp::{max_by_key, min_by_key};
+use advent_of_code::input::ParseError;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::path::Path;
+use std::str::FromStr;
 
 fn main() {
     if let Ok(lines) = read_lines("./data/day1.txt") {
@@ -25,24 +26,7 @@ where
 }
 
 fn process_line(line: &str) -> i32 {
-    let digits_structs = look_for_digits(line);
-    if let Some(digit_words_structs) = look_for_digit_words(line) {
-        min_by_key(
-            digits_structs.0,
-            digit_words_structs.0,
-            |x: &DigitFindingResult| x.position,
-        )
-        .value
-            * 10
-            + max_by_key(
-                digits_structs.1,
-                digit_words_structs.1,
-                |x: &DigitFindingResult| x.position,
-            )
-            .value
-    } else {
-        digits_structs.0.value * 10 + digits_structs.1.value
-    }
+    line.parse::<CalibrationLine>().unwrap().value()
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -51,6 +35,40 @@ struct DigitFindingResult {
     position: usize,
 }
 
+/// First and last digit of a line, spelled out or not.
+#[derive(Debug, PartialEq, Copy, Clone)]
+struct CalibrationLine {
+    first: DigitFindingResult,
+    last: DigitFindingResult,
+}
+
+impl CalibrationLine {
+    fn value(&self) -> i32 {
+        self.first.value * 10 + self.last.value
+    }
+}
+
+impl FromStr for CalibrationLine {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let digits = line
+            .contains(|x: char| x.is_ascii_digit())
+            .then(|| look_for_digits(line));
+        let found = digits
+            .into_iter()
+            .chain(look_for_digit_words(line))
+            .collect::<Vec<(DigitFindingResult, DigitFindingResult)>>();
+        match (
+            found.iter().map(|x| x.0).min_by_key(|x| x.position),
+            found.iter().map(|x| x.1).max_by_key(|x| x.position),
+        ) {
+            (Some(first), Some(last)) => Ok(CalibrationLine { first, last }),
+            _ => Err(ParseError::new(line, "no digits")),
+        }
+    }
+}
+
 fn look_for_digits(line: &str) -> (DigitFindingResult, DigitFindingResult) {
     let first_position = line.find(|x: char| x.is_ascii_digit()).unwrap();
     let last_position = line.rfind(|x: char| x.is_ascii_digit()).unwrap();
@@ -124,6 +142,28 @@ mod tests {
         assert_eq!(process_line("4nineeightseven2"), 42);
         assert_eq!(process_line("zoneight234"), 14);
         assert_eq!(process_line("7pqrstsixteen"), 76);
+        assert_eq!(process_line("eightwothree"), 83);
+    }
+
+    #[test]
+    fn test_calibration_line_from_str() {
+        assert_eq!(
+            "two1nine".parse::<CalibrationLine>(),
+            Ok(CalibrationLine {
+                first: DigitFindingResult {
+                    value: 2,
+                    position: 0
+                },
+                last: DigitFindingResult {
+                    value: 9,
+                    position: 4
+                }
+            })
+        );
+        assert_eq!(
+            "abcdef".parse::<CalibrationLine>().unwrap_err().reason(),
+            "no digits"
+        );
     }
 
     #[test]
@@ -0,0 +1,259 @@
+use advent_of_code::grid::{Direction, Grid, Position};
+use advent_of_code::polygon::interior_points;
+use std::env;
+use std::fs::read_to_string;
+
+fn main() {
+    let maze = PipeMaze::parse(&read_to_string("./data/day10.txt").unwrap());
+    let args = env::args().collect::<Vec<String>>();
+    if args.iter().any(|x| x == "--render") {
+        print!("{}", maze.render_loop());
+    }
+    let strategy = match args.iter().any(|x| x == "--shoelace") {
+        true => AreaStrategy::Shoelace,
+        false => AreaStrategy::RayCasting,
+    };
+    println!("{}", maze.farthest_distance());
+    println!("{}", maze.enclosed_tiles(strategy));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pipe {
+    Vertical,
+    Horizontal,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+    Ground,
+    Start,
+}
+
+impl Pipe {
+    fn from_char(value: char) -> Self {
+        match value {
+            '|' => Pipe::Vertical,
+            '-' => Pipe::Horizontal,
+            'L' => Pipe::NorthEast,
+            'J' => Pipe::NorthWest,
+            '7' => Pipe::SouthWest,
+            'F' => Pipe::SouthEast,
+            'S' => Pipe::Start,
+            _ => Pipe::Ground,
+        }
+    }
+
+    fn from_connections(a: Direction, b: Direction) -> Self {
+        use Direction::*;
+        match (a.min(b), a.max(b)) {
+            (North, South) => Pipe::Vertical,
+            (East, West) => Pipe::Horizontal,
+            (North, East) => Pipe::NorthEast,
+            (North, West) => Pipe::NorthWest,
+            (South, West) => Pipe::SouthWest,
+            (East, South) => Pipe::SouthEast,
+            _ => Pipe::Ground,
+        }
+    }
+
+    fn connections(self) -> Option<[Direction; 2]> {
+        use Direction::*;
+        match self {
+            Pipe::Vertical => Some([North, South]),
+            Pipe::Horizontal => Some([East, West]),
+            Pipe::NorthEast => Some([North, East]),
+            Pipe::NorthWest => Some([North, West]),
+            Pipe::SouthWest => Some([South, West]),
+            Pipe::SouthEast => Some([East, South]),
+            Pipe::Ground | Pipe::Start => None,
+        }
+    }
+
+    fn connects(self, direction: Direction) -> bool {
+        self.connections().is_some_and(|x| x.contains(&direction))
+    }
+
+    fn box_drawing(self) -> char {
+        match self {
+            Pipe::Vertical => '│',
+            Pipe::Horizontal => '─',
+            Pipe::NorthEast => '└',
+            Pipe::NorthWest => '┘',
+            Pipe::SouthWest => '┐',
+            Pipe::SouthEast => '┌',
+            Pipe::Ground => ' ',
+            Pipe::Start => 'S',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AreaStrategy {
+    RayCasting,
+    Shoelace,
+}
+
+#[derive(Debug)]
+struct PipeMaze {
+    grid: Grid<Pipe>,
+    start: Position,
+}
+
+impl PipeMaze {
+    fn parse(text: &str) -> Self {
+        let mut grid = Grid::parse_with(text, Pipe::from_char);
+        let start = grid.find(|&x| x == Pipe::Start).unwrap();
+        let connected = grid
+            .neighbours(start)
+            .filter(|(d, p)| grid[*p].connects(d.opposite()))
+            .map(|(d, _)| d)
+            .collect::<Vec<Direction>>();
+        assert_eq!(
+            connected.len(),
+            2,
+            "start tile must connect to exactly two pipes"
+        );
+        grid[start] = Pipe::from_connections(connected[0], connected[1]);
+        Self { grid, start }
+    }
+
+    fn start_pipe(&self) -> Pipe {
+        self.grid[self.start]
+    }
+
+    fn trace_loop(&self) -> Vec<Position> {
+        let mut path = vec![self.start];
+        let mut direction = self.start_pipe().connections().unwrap()[0];
+        let mut position = direction.step(self.start);
+        while position != self.start {
+            path.push(position);
+            let [a, b] = self.grid[position].connections().unwrap();
+            direction = if a == direction.opposite() { b } else { a };
+            position = direction.step(position);
+        }
+        path
+    }
+
+    fn farthest_distance(&self) -> usize {
+        self.trace_loop().len() / 2
+    }
+
+    fn loop_mask(&self) -> Grid<bool> {
+        let mut mask = self.grid.map(|_| false);
+        for position in self.trace_loop() {
+            mask[position] = true;
+        }
+        mask
+    }
+
+    fn enclosed_tiles(&self, strategy: AreaStrategy) -> usize {
+        match strategy {
+            AreaStrategy::RayCasting => self.enclosed_by_ray_casting(),
+            AreaStrategy::Shoelace => self.enclosed_by_shoelace(),
+        }
+    }
+
+    fn enclosed_by_ray_casting(&self) -> usize {
+        let mask = self.loop_mask();
+        let mut count = 0;
+        for y in 0..self.grid.height() as i32 {
+            let mut inside = false;
+            for x in 0..self.grid.width() as i32 {
+                if mask[(x, y)] {
+                    if self.grid[(x, y)].connects(Direction::North) {
+                        inside = !inside;
+                    }
+                } else if inside {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    fn enclosed_by_shoelace(&self) -> usize {
+        let vertices = self
+            .trace_loop()
+            .into_iter()
+            .map(|(x, y)| (x as i64, y as i64))
+            .collect::<Vec<_>>();
+        interior_points(&vertices) as usize
+    }
+
+    fn render_loop(&self) -> String {
+        let mask = self.loop_mask();
+        self.grid
+            .iter()
+            .map(|(p, pipe)| {
+                let ch = match mask[p] {
+                    true if p == self.start => 'S',
+                    true => pipe.box_drawing(),
+                    false => '·',
+                };
+                match p.0 as usize == self.grid.width() - 1 {
+                    true => format!("{}\n", ch),
+                    false => ch.to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIMPLE_LOOP: &str = "-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF";
+    const COMPLEX_LOOP: &str = "..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...";
+    const SQUEEZED: &str = "..........\n.S------7.\n.|F----7|.\n.||....||.\n.||....||.\n.|L-7F-J|.\n.|..||..|.\n.L--JL--J.\n..........";
+    const LARGER: &str = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+    const JUNK: &str = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+
+    #[test]
+    fn test_infer_start_pipe() {
+        assert_eq!(PipeMaze::parse(SIMPLE_LOOP).start_pipe(), Pipe::SouthEast);
+        assert_eq!(PipeMaze::parse(COMPLEX_LOOP).start_pipe(), Pipe::SouthEast);
+        assert_eq!(PipeMaze::parse(JUNK).start_pipe(), Pipe::SouthWest);
+    }
+
+    #[test]
+    fn test_day10_part1() {
+        assert_eq!(PipeMaze::parse(SIMPLE_LOOP).farthest_distance(), 4);
+        assert_eq!(PipeMaze::parse(COMPLEX_LOOP).farthest_distance(), 8);
+    }
+
+    #[test]
+    fn test_day10_part2() {
+        for strategy in [AreaStrategy::RayCasting, AreaStrategy::Shoelace] {
+            assert_eq!(PipeMaze::parse(SQUEEZED).enclosed_tiles(strategy), 4);
+            assert_eq!(PipeMaze::parse(LARGER).enclosed_tiles(strategy), 8);
+            assert_eq!(PipeMaze::parse(JUNK).enclosed_tiles(strategy), 10);
+        }
+    }
+
+    #[test]
+    fn test_render_loop() {
+        assert_eq!(
+            PipeMaze::parse(SIMPLE_LOOP).render_loop(),
+            "·····\n·S─┐·\n·│·│·\n·└─┘·\n·····\n"
+        );
+    }
+}
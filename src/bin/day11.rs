@@ -0,0 +1,113 @@
+use std::fs::read_to_string;
+
+fn main() {
+    let galaxies = parse_galaxies(&read_to_string("./data/day11.txt").unwrap());
+    println!("{}", sum_shortest_paths(&expand(&galaxies, 2)));
+    println!("{}", sum_shortest_paths(&expand(&galaxies, 1_000_000)));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Galaxy {
+    x: u64,
+    y: u64,
+}
+
+fn parse_galaxies(lines: &str) -> Vec<Galaxy> {
+    lines
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, ch)| *ch == '#')
+                .map(move |(x, _)| Galaxy {
+                    x: x as u64,
+                    y: y as u64,
+                })
+        })
+        .collect()
+}
+
+/// Every empty line becomes `factor` lines, so 1 leaves the axis as is and 0 drops empty lines.
+fn expand_axis(coordinates: &[u64], factor: u64) -> Vec<u64> {
+    let mut occupied = coordinates.to_vec();
+    occupied.sort_unstable();
+    occupied.dedup();
+    coordinates
+        .iter()
+        .map(|&c| {
+            let empty_before = c - occupied.partition_point(|&x| x < c) as u64;
+            c - empty_before + empty_before * factor
+        })
+        .collect()
+}
+
+fn expand(galaxies: &[Galaxy], factor: u64) -> Vec<Galaxy> {
+    let xs = expand_axis(&galaxies.iter().map(|g| g.x).collect::<Vec<u64>>(), factor);
+    let ys = expand_axis(&galaxies.iter().map(|g| g.y).collect::<Vec<u64>>(), factor);
+    xs.into_iter()
+        .zip(ys)
+        .map(|(x, y)| Galaxy { x, y })
+        .collect()
+}
+
+fn sum_pairwise_differences(mut values: Vec<u64>) -> u64 {
+    values.sort_unstable();
+    let mut prefix = 0;
+    let mut total = 0;
+    for (i, value) in values.into_iter().enumerate() {
+        total += value * i as u64 - prefix;
+        prefix += value;
+    }
+    total
+}
+
+fn sum_shortest_paths(galaxies: &[Galaxy]) -> u64 {
+    sum_pairwise_differences(galaxies.iter().map(|g| g.x).collect())
+        + sum_pairwise_differences(galaxies.iter().map(|g| g.y).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn test_expand() {
+        let galaxies = parse_galaxies(EXAMPLE);
+        assert_eq!(galaxies[0], Galaxy { x: 3, y: 0 });
+        assert_eq!(expand(&galaxies, 2)[0], Galaxy { x: 4, y: 0 });
+        assert_eq!(expand(&galaxies, 2)[8], Galaxy { x: 5, y: 11 });
+        assert_eq!(expand(&galaxies, 1)[8], Galaxy { x: 4, y: 9 });
+        assert_eq!(expand(&galaxies, 0)[8], Galaxy { x: 3, y: 7 });
+    }
+
+    #[test]
+    fn test_sum_pairwise_differences() {
+        let values: Vec<u64> = vec![7, 1, 4, 4, 10];
+        let brute_force = values
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| values[i + 1..].iter().map(move |b| a.abs_diff(*b)))
+            .sum::<u64>();
+        assert_eq!(sum_pairwise_differences(values), brute_force);
+    }
+
+    #[test]
+    fn test_day11() {
+        let galaxies = parse_galaxies(EXAMPLE);
+        assert_eq!(sum_shortest_paths(&expand(&galaxies, 2)), 374);
+        assert_eq!(sum_shortest_paths(&expand(&galaxies, 10)), 1030);
+        assert_eq!(sum_shortest_paths(&expand(&galaxies, 100)), 8410);
+    }
+}
@@ -0,0 +1,181 @@
+use std::fs::read_to_string;
+
+fn main() {
+    let records = read_to_string("./data/day12.txt")
+        .unwrap()
+        .lines()
+        .map(parse_record)
+        .collect::<Vec<SpringRecord>>();
+    println!(
+        "{}",
+        records.iter().map(|x| x.count_arrangements()).sum::<u128>()
+    );
+    println!(
+        "{}",
+        records
+            .iter()
+            .map(|x| x.unfold(5).count_arrangements())
+            .sum::<u128>()
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Spring {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SpringRecord {
+    springs: Vec<Spring>,
+    groups: Vec<usize>,
+}
+
+fn parse_record(line: &str) -> SpringRecord {
+    let mut splitted = line.split_whitespace();
+    let (springs_part, groups_part) = (splitted.next().unwrap(), splitted.next().unwrap());
+    let springs = springs_part
+        .chars()
+        .map(|x| match x {
+            '.' => Spring::Operational,
+            '#' => Spring::Damaged,
+            '?' => Spring::Unknown,
+            _ => panic!("unknown spring condition {}", x),
+        })
+        .collect();
+    let groups = groups_part
+        .split(',')
+        .map(|x| x.parse::<usize>().unwrap())
+        .collect();
+    SpringRecord { springs, groups }
+}
+
+impl SpringRecord {
+    fn unfold(&self, times: usize) -> SpringRecord {
+        let mut springs = Vec::new();
+        for i in 0..times {
+            if i > 0 {
+                springs.push(Spring::Unknown);
+            }
+            springs.extend_from_slice(&self.springs);
+        }
+        SpringRecord {
+            springs,
+            groups: self.groups.repeat(times),
+        }
+    }
+
+    fn count_arrangements(&self) -> u128 {
+        let mut memo = vec![vec![None; self.groups.len() + 1]; self.springs.len() + 1];
+        self.count_from(0, 0, &mut memo)
+    }
+
+    fn count_from(&self, start: usize, group: usize, memo: &mut Vec<Vec<Option<u128>>>) -> u128 {
+        if let Some(count) = memo[start][group] {
+            return count;
+        }
+        let springs = &self.springs;
+        let count = if group == self.groups.len() {
+            match springs[start..].contains(&Spring::Damaged) {
+                true => 0,
+                false => 1,
+            }
+        } else if start >= springs.len() {
+            0
+        } else {
+            let mut count = 0;
+            if springs[start] != Spring::Damaged {
+                count += self.count_from(start + 1, group, memo);
+            }
+            let end = start + self.groups[group];
+            if springs[start] != Spring::Operational
+                && end <= springs.len()
+                && !springs[start..end].contains(&Spring::Operational)
+                && springs.get(end) != Some(&Spring::Damaged)
+            {
+                count += self.count_from((end + 1).min(springs.len()), group + 1, memo);
+            }
+            count
+        };
+        memo[start][group] = Some(count);
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [(&str, u128, u128); 6] = [
+        ("???.### 1,1,3", 1, 1),
+        (".??..??...?##. 1,1,3", 4, 16384),
+        ("?#?#?#?#?#?#?#? 1,3,1,6", 1, 1),
+        ("????.#...#... 4,1,1", 1, 16),
+        ("????.######..#####. 1,6,5", 4, 2500),
+        ("?###???????? 3,2,1", 10, 506250),
+    ];
+
+    fn brute_force_arrangements(record: &SpringRecord) -> u128 {
+        let unknown = record
+            .springs
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| **x == Spring::Unknown)
+            .map(|(i, _)| i)
+            .collect::<Vec<usize>>();
+        (0..1u32 << unknown.len())
+            .filter(|mask| {
+                let mut springs = record.springs.clone();
+                for (bit, &i) in unknown.iter().enumerate() {
+                    springs[i] = match mask >> bit & 1 {
+                        1 => Spring::Damaged,
+                        _ => Spring::Operational,
+                    };
+                }
+                let groups = springs
+                    .split(|x| *x == Spring::Operational)
+                    .map(|x| x.len())
+                    .filter(|x| *x > 0)
+                    .collect::<Vec<usize>>();
+                groups == record.groups
+            })
+            .count() as u128
+    }
+
+    #[test]
+    fn test_parse_record() {
+        assert_eq!(
+            parse_record("#.? 1,1"),
+            SpringRecord {
+                springs: vec![Spring::Damaged, Spring::Operational, Spring::Unknown],
+                groups: vec![1, 1]
+            }
+        );
+    }
+
+    #[test]
+    fn test_count_matches_brute_force() {
+        for (line, _, _) in EXAMPLE {
+            let record = parse_record(line);
+            assert_eq!(
+                record.count_arrangements(),
+                brute_force_arrangements(&record)
+            );
+            let unfolded = record.unfold(2);
+            assert_eq!(
+                unfolded.count_arrangements(),
+                brute_force_arrangements(&unfolded)
+            );
+        }
+    }
+
+    #[test]
+    fn test_day12() {
+        for (line, folded, unfolded) in EXAMPLE {
+            let record = parse_record(line);
+            assert_eq!(record.count_arrangements(), folded);
+            assert_eq!(record.unfold(5).count_arrangements(), unfolded);
+        }
+    }
+}
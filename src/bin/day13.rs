@@ -0,0 +1,127 @@
+use advent_of_code::input::paragraphs;
+use std::fs::read_to_string;
+
+fn main() {
+    let patterns = parse_patterns(&read_to_string("./data/day13.txt").unwrap());
+    println!("{}", summarize(&patterns, 0));
+    println!("{}", summarize(&patterns, 1));
+}
+
+#[derive(Debug, PartialEq)]
+enum Reflection {
+    Vertical(usize),
+    Horizontal(usize),
+}
+
+impl Reflection {
+    fn summary(&self) -> usize {
+        match self {
+            Reflection::Vertical(columns) => *columns,
+            Reflection::Horizontal(rows) => 100 * rows,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Pattern {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+fn parse_pattern(block: &str) -> Pattern {
+    let lines = block.lines().collect::<Vec<&str>>();
+    let width = lines[0].len();
+    assert!(
+        width <= u64::BITS as usize && lines.len() <= u64::BITS as usize,
+        "patterns are packed into u64 masks, {}x{} is too big",
+        width,
+        lines.len()
+    );
+    let mut rows = vec![0; lines.len()];
+    let mut columns = vec![0; width];
+    for (y, line) in lines.iter().enumerate() {
+        for (x, ch) in line.chars().enumerate() {
+            if ch == '#' {
+                rows[y] |= 1 << x;
+                columns[x] |= 1 << y;
+            }
+        }
+    }
+    Pattern { rows, columns }
+}
+
+fn parse_patterns(text: &str) -> Vec<Pattern> {
+    paragraphs(text).map(parse_pattern).collect()
+}
+
+fn find_mirror(lines: &[u64], smudges: u32) -> Option<usize> {
+    (1..lines.len()).find(|&split| {
+        lines[..split]
+            .iter()
+            .rev()
+            .zip(&lines[split..])
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum::<u32>()
+            == smudges
+    })
+}
+
+impl Pattern {
+    fn reflection(&self, smudges: u32) -> Option<Reflection> {
+        find_mirror(&self.columns, smudges)
+            .map(Reflection::Vertical)
+            .or_else(|| find_mirror(&self.rows, smudges).map(Reflection::Horizontal))
+    }
+}
+
+fn summarize(patterns: &[Pattern], smudges: u32) -> usize {
+    patterns
+        .iter()
+        .map(|x| x.reflection(smudges).unwrap().summary())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+";
+
+    #[test]
+    fn test_reflection() {
+        let patterns = parse_patterns(EXAMPLE);
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(patterns[0].reflection(0), Some(Reflection::Vertical(5)));
+        assert_eq!(patterns[1].reflection(0), Some(Reflection::Horizontal(4)));
+        assert_eq!(patterns[0].reflection(1), Some(Reflection::Horizontal(3)));
+        assert_eq!(patterns[1].reflection(1), Some(Reflection::Horizontal(1)));
+    }
+
+    #[test]
+    fn test_day13() {
+        let patterns = parse_patterns(EXAMPLE);
+        assert_eq!(summarize(&patterns, 0), 405);
+        assert_eq!(summarize(&patterns, 1), 400);
+    }
+
+    #[test]
+    #[should_panic(expected = "65x1 is too big")]
+    fn test_parse_oversized_pattern() {
+        parse_pattern(&"#".repeat(65));
+    }
+}
@@ -0,0 +1,128 @@
+use advent_of_code::cycle::nth_state;
+use advent_of_code::grid::{Direction, Grid, Position};
+use std::fs::read_to_string;
+
+fn main() {
+    let platform = parse_platform(&read_to_string("./data/day14.txt").unwrap());
+    let mut tilted = platform.clone();
+    tilt(&mut tilted, Direction::North);
+    println!("{}", north_load(&tilted));
+    println!(
+        "{}",
+        north_load(&nth_state(platform, 1_000_000_000, spin_cycle))
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Rock {
+    Round,
+    Cube,
+    Empty,
+}
+
+fn parse_platform(lines: &str) -> Grid<Rock> {
+    Grid::parse_with(lines, |x| match x {
+        'O' => Rock::Round,
+        '#' => Rock::Cube,
+        _ => Rock::Empty,
+    })
+}
+
+fn tilt(platform: &mut Grid<Rock>, direction: Direction) {
+    let (width, height) = (platform.width() as i32, platform.height() as i32);
+    let (lines, length) = match direction {
+        Direction::North | Direction::South => (width, height),
+        Direction::East | Direction::West => (height, width),
+    };
+    let position = |line: i32, k: i32| -> Position {
+        match direction {
+            Direction::North => (line, k),
+            Direction::South => (line, height - 1 - k),
+            Direction::West => (k, line),
+            Direction::East => (width - 1 - k, line),
+        }
+    };
+    for line in 0..lines {
+        let mut free = 0;
+        for k in 0..length {
+            match platform[position(line, k)] {
+                Rock::Cube => free = k + 1,
+                Rock::Round => {
+                    platform[position(line, k)] = Rock::Empty;
+                    platform[position(line, free)] = Rock::Round;
+                    free += 1;
+                }
+                Rock::Empty => {}
+            }
+        }
+    }
+}
+
+fn spin_cycle(platform: &mut Grid<Rock>) {
+    for direction in [
+        Direction::North,
+        Direction::West,
+        Direction::South,
+        Direction::East,
+    ] {
+        tilt(platform, direction);
+    }
+}
+
+fn north_load(platform: &Grid<Rock>) -> usize {
+    platform
+        .iter()
+        .filter(|(_, rock)| **rock == Rock::Round)
+        .map(|(p, _)| platform.height() - p.1 as usize)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....";
+
+    #[test]
+    fn test_spin_cycle() {
+        let mut platform = parse_platform(EXAMPLE);
+        spin_cycle(&mut platform);
+        assert_eq!(
+            platform,
+            parse_platform(
+                ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#...."
+            )
+        );
+    }
+
+    #[test]
+    fn test_day14_part1() {
+        let mut platform = parse_platform(EXAMPLE);
+        tilt(&mut platform, Direction::North);
+        assert_eq!(north_load(&platform), 136);
+    }
+
+    #[test]
+    fn test_day14_part2() {
+        let platform = nth_state(parse_platform(EXAMPLE), 1_000_000_000, spin_cycle);
+        assert_eq!(north_load(&platform), 64);
+    }
+}
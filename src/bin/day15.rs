@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::hash::Hasher;
+
+fn main() {
+    let file = read_to_string("./data/day15.txt").unwrap();
+    println!(
+        "{}",
+        parse_steps(&file)
+            .map(|x| holiday_hash(x) as u32)
+            .sum::<u32>()
+    );
+    let mut map = LensMap::new();
+    for step in parse_steps(&file) {
+        map.apply(parse_operation(step));
+    }
+    println!("{}", map.focusing_power());
+}
+
+#[derive(Debug, Default)]
+struct HolidayHasher {
+    value: u8,
+}
+
+impl Hasher for HolidayHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.value = self.value.wrapping_add(*byte).wrapping_mul(17);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.value as u64
+    }
+}
+
+fn holiday_hash(step: &str) -> u8 {
+    let mut hasher = HolidayHasher::default();
+    hasher.write(step.as_bytes());
+    hasher.finish() as u8
+}
+
+fn parse_steps(line: &str) -> impl Iterator<Item = &str> {
+    line.split(',')
+        .map(|x| x.trim_matches(['\r', '\n']))
+        .filter(|x| !x.is_empty())
+}
+
+#[derive(Debug, PartialEq)]
+enum Operation<'a> {
+    Remove(&'a str),
+    Insert(&'a str, u8),
+}
+
+fn parse_operation(step: &str) -> Operation<'_> {
+    match step.split_once('=') {
+        Some((label, focal_length)) => {
+            Operation::Insert(label, focal_length.parse::<u8>().unwrap())
+        }
+        None => Operation::Remove(step.trim_end_matches('-')),
+    }
+}
+
+#[derive(Debug, Default)]
+struct LensBox<'a> {
+    slots: Vec<Option<(&'a str, u8)>>,
+    index: HashMap<&'a str, usize>,
+}
+
+impl<'a> LensBox<'a> {
+    fn insert(&mut self, label: &'a str, focal_length: u8) {
+        match self.index.get(label) {
+            Some(&i) => self.slots[i] = Some((label, focal_length)),
+            None => {
+                self.index.insert(label, self.slots.len());
+                self.slots.push(Some((label, focal_length)));
+            }
+        }
+    }
+
+    fn remove(&mut self, label: &str) {
+        if let Some(i) = self.index.remove(label) {
+            self.slots[i] = None;
+            if self.slots.len() > 2 * self.index.len() + 8 {
+                self.compact();
+            }
+        }
+    }
+
+    fn compact(&mut self) {
+        self.slots.retain(Option::is_some);
+        for (i, (label, _)) in self.slots.iter().flatten().enumerate() {
+            self.index.insert(label, i);
+        }
+    }
+
+    fn lenses(&self) -> impl Iterator<Item = (&'a str, u8)> + '_ {
+        self.slots.iter().flatten().copied()
+    }
+}
+
+#[derive(Debug)]
+struct LensMap<'a> {
+    boxes: Vec<LensBox<'a>>,
+}
+
+impl<'a> LensMap<'a> {
+    fn new() -> Self {
+        Self {
+            boxes: (0..256).map(|_| LensBox::default()).collect(),
+        }
+    }
+
+    fn apply(&mut self, operation: Operation<'a>) {
+        match operation {
+            Operation::Insert(label, focal_length) => {
+                self.boxes[holiday_hash(label) as usize].insert(label, focal_length)
+            }
+            Operation::Remove(label) => self.boxes[holiday_hash(label) as usize].remove(label),
+        }
+    }
+
+    fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(i, lens_box)| {
+                lens_box
+                    .lenses()
+                    .enumerate()
+                    .map(move |(slot, (_, focal_length))| {
+                        (i + 1) * (slot + 1) * focal_length as usize
+                    })
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+
+    #[test]
+    fn test_holiday_hash() {
+        assert_eq!(holiday_hash("HASH"), 52);
+        assert_eq!(holiday_hash("rn=1"), 30);
+        assert_eq!(holiday_hash("rn"), 0);
+        assert_eq!(holiday_hash("qp"), 1);
+    }
+
+    #[test]
+    fn test_lens_box_order() {
+        let mut lens_box = LensBox::default();
+        for i in 0..20u8 {
+            lens_box.insert(["a", "b", "c", "d"][i as usize % 4], i);
+            lens_box.remove(["b", "c"][i as usize % 2]);
+        }
+        assert_eq!(
+            lens_box.lenses().collect::<Vec<_>>(),
+            vec![("a", 16), ("d", 19)]
+        );
+    }
+
+    #[test]
+    fn test_day15() {
+        assert_eq!(
+            parse_steps(EXAMPLE)
+                .map(|x| holiday_hash(x) as u32)
+                .sum::<u32>(),
+            1320
+        );
+        let mut map = LensMap::new();
+        for step in parse_steps(EXAMPLE) {
+            map.apply(parse_operation(step));
+        }
+        assert_eq!(
+            map.boxes[3].lenses().collect::<Vec<_>>(),
+            vec![("ot", 7), ("ab", 5), ("pc", 6)]
+        );
+        assert_eq!(map.focusing_power(), 145);
+    }
+}
@@ -0,0 +1,257 @@
+use advent_of_code::grid::{Direction, Grid, Position};
+use advent_of_code::search::strongly_connected_components;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+fn main() {
+    let contraption = parse_contraption(&read_to_string("./data/day16.txt").unwrap());
+    let tracer = BeamTracer::new(&contraption);
+    println!("{}", tracer.energised((0, 0), Direction::East));
+    println!("{}", max_energised(&contraption));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tile {
+    Empty,
+    ForwardMirror,
+    BackwardMirror,
+    VerticalSplitter,
+    HorizontalSplitter,
+}
+
+fn parse_contraption(lines: &str) -> Grid<Tile> {
+    Grid::parse_with(lines, |x| match x {
+        '/' => Tile::ForwardMirror,
+        '\\' => Tile::BackwardMirror,
+        '|' => Tile::VerticalSplitter,
+        '-' => Tile::HorizontalSplitter,
+        _ => Tile::Empty,
+    })
+}
+
+impl Tile {
+    fn outgoing(self, direction: Direction) -> (Direction, Option<Direction>) {
+        use Direction::*;
+        match (self, direction) {
+            (Tile::ForwardMirror, East) => (North, None),
+            (Tile::ForwardMirror, North) => (East, None),
+            (Tile::ForwardMirror, West) => (South, None),
+            (Tile::ForwardMirror, South) => (West, None),
+            (Tile::BackwardMirror, East) => (South, None),
+            (Tile::BackwardMirror, South) => (East, None),
+            (Tile::BackwardMirror, West) => (North, None),
+            (Tile::BackwardMirror, North) => (West, None),
+            (Tile::VerticalSplitter, East | West) => (North, Some(South)),
+            (Tile::HorizontalSplitter, North | South) => (East, Some(West)),
+            _ => (direction, None),
+        }
+    }
+}
+
+/// Counts energised tiles from any entry point, sharing the work between entries.
+///
+/// A splitter sends out the same two beams whichever side it's hit from, so the tiles
+/// energised once a beam gets split only depend on the splitter. Those sets are computed
+/// once per group of splitters that feed each other, and every entry only traces its own
+/// beam up to the first split.
+struct BeamTracer<'a> {
+    contraption: &'a Grid<Tile>,
+    component: HashMap<usize, usize>,
+    /// Bitset of the cells energised after a split in each component of splitters.
+    reachable: Vec<Vec<u64>>,
+}
+
+impl<'a> BeamTracer<'a> {
+    fn new(contraption: &'a Grid<Tile>) -> Self {
+        let mut tracer = Self {
+            contraption,
+            component: HashMap::new(),
+            reachable: Vec::new(),
+        };
+        let mut splits = HashMap::new();
+        for (position, &tile) in contraption.iter() {
+            let outputs = match tile {
+                Tile::VerticalSplitter => [Direction::North, Direction::South],
+                Tile::HorizontalSplitter => [Direction::East, Direction::West],
+                _ => continue,
+            };
+            let mut cells = vec![tracer.cell(position)];
+            let mut next = Vec::new();
+            for direction in outputs {
+                let (path, split) = tracer.follow(direction.step(position), direction);
+                cells.extend(path);
+                next.extend(split);
+            }
+            splits.insert(tracer.cell(position), (cells, next));
+        }
+        let components =
+            strongly_connected_components(splits.keys().copied(), |x| splits[x].1.clone());
+        for (i, members) in components.iter().enumerate() {
+            let mut reachable = vec![0; tracer.bitset_len()];
+            for member in members {
+                let (cells, next) = &splits[member];
+                for &cell in cells {
+                    reachable[cell / 64] |= 1 << (cell % 64);
+                }
+                // components come successors first, so theirs are already complete
+                for &j in next.iter().filter_map(|x| tracer.component.get(x)) {
+                    for (word, other) in reachable.iter_mut().zip(&tracer.reachable[j]) {
+                        *word |= other;
+                    }
+                }
+            }
+            for &member in members {
+                tracer.component.insert(member, i);
+            }
+            tracer.reachable.push(reachable);
+        }
+        tracer
+    }
+
+    fn cell(&self, position: Position) -> usize {
+        position.1 as usize * self.contraption.width() + position.0 as usize
+    }
+
+    fn bitset_len(&self) -> usize {
+        (self.contraption.width() * self.contraption.height()).div_ceil(64)
+    }
+
+    /// Cells a single beam passes until it leaves the contraption or gets split, and the cell
+    /// of the splitter that splits it. An unsplit beam that runs for more steps than there are
+    /// beam states is going round in circles, so that's where tracing stops.
+    fn follow(
+        &self,
+        mut position: Position,
+        mut direction: Direction,
+    ) -> (Vec<usize>, Option<usize>) {
+        let mut cells = Vec::new();
+        for _ in 0..self.bitset_len() * 64 * 4 {
+            let Some(&tile) = self.contraption.get(position) else {
+                break;
+            };
+            let cell = self.cell(position);
+            cells.push(cell);
+            match tile.outgoing(direction) {
+                (_, Some(_)) => return (cells, Some(cell)),
+                (next, None) => (position, direction) = (next.step(position), next),
+            }
+        }
+        (cells, None)
+    }
+
+    fn energised(&self, start: Position, direction: Direction) -> usize {
+        let (cells, split) = self.follow(start, direction);
+        let mut energised = match split {
+            Some(cell) => self.reachable[self.component[&cell]].clone(),
+            None => vec![0; self.bitset_len()],
+        };
+        for cell in cells {
+            energised[cell / 64] |= 1 << (cell % 64);
+        }
+        energised.iter().map(|x| x.count_ones() as usize).sum()
+    }
+}
+
+fn edge_entries(contraption: &Grid<Tile>) -> Vec<(Position, Direction)> {
+    let (width, height) = (contraption.width() as i32, contraption.height() as i32);
+    (0..width)
+        .flat_map(|x| {
+            [
+                ((x, 0), Direction::South),
+                ((x, height - 1), Direction::North),
+            ]
+        })
+        .chain(
+            (0..height)
+                .flat_map(|y| [((0, y), Direction::East), ((width - 1, y), Direction::West)]),
+        )
+        .collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn max_energised(contraption: &Grid<Tile>) -> usize {
+    let tracer = BeamTracer::new(contraption);
+    edge_entries(contraption)
+        .into_iter()
+        .map(|(position, direction)| tracer.energised(position, direction))
+        .max()
+        .unwrap()
+}
+
+#[cfg(feature = "parallel")]
+fn max_energised(contraption: &Grid<Tile>) -> usize {
+    let tracer = BeamTracer::new(contraption);
+    edge_entries(contraption)
+        .into_par_iter()
+        .map(|(position, direction)| tracer.energised(position, direction))
+        .max()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    fn simulate(contraption: &Grid<Tile>, start: Position, direction: Direction) -> usize {
+        let mut visited = HashSet::new();
+        let mut beams = vec![(start, direction)];
+        while let Some((position, direction)) = beams.pop() {
+            let Some(&tile) = contraption.get(position) else {
+                continue;
+            };
+            if !visited.insert((position, direction)) {
+                continue;
+            }
+            let (first, second) = tile.outgoing(direction);
+            beams.extend(
+                [first]
+                    .into_iter()
+                    .chain(second)
+                    .map(|x| (x.step(position), x)),
+            );
+        }
+        visited.iter().map(|(p, _)| p).collect::<HashSet<_>>().len()
+    }
+
+    #[test]
+    fn test_shared_tracing() {
+        let looping = "/-\\\n|.|\n\\-/";
+        for contraption in [EXAMPLE, looping].map(parse_contraption) {
+            let tracer = BeamTracer::new(&contraption);
+            for (position, direction) in edge_entries(&contraption) {
+                assert_eq!(
+                    tracer.energised(position, direction),
+                    simulate(&contraption, position, direction)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_day16_part1() {
+        let contraption = parse_contraption(EXAMPLE);
+        assert_eq!(
+            BeamTracer::new(&contraption).energised((0, 0), Direction::East),
+            46
+        );
+    }
+
+    #[test]
+    fn test_day16_part2() {
+        assert_eq!(max_energised(&parse_contraption(EXAMPLE)), 51);
+    }
+}
@@ -0,0 +1,120 @@
+use advent_of_code::grid::{Direction, Grid, Position};
+use advent_of_code::search::astar;
+use std::fs::read_to_string;
+
+fn main() {
+    let city = parse_city(&read_to_string("./data/day17.txt").unwrap());
+    println!("{}", least_heat_loss(&city, Crucible::REGULAR));
+    println!("{}", least_heat_loss(&city, Crucible::ULTRA));
+}
+
+fn parse_city(lines: &str) -> Grid<u32> {
+    Grid::parse_with(lines, |x| x.to_digit(10).unwrap())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Crucible {
+    min_run: u8,
+    max_run: u8,
+}
+
+impl Crucible {
+    const REGULAR: Crucible = Crucible {
+        min_run: 1,
+        max_run: 3,
+    };
+    const ULTRA: Crucible = Crucible {
+        min_run: 4,
+        max_run: 10,
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct State {
+    position: Position,
+    direction: Option<Direction>,
+    run: u8,
+}
+
+fn successors(city: &Grid<u32>, crucible: Crucible, state: &State) -> Vec<(State, u32)> {
+    let directions = match state.direction {
+        None => Direction::ALL.to_vec(),
+        Some(d) if state.run < crucible.min_run => vec![d],
+        Some(d) if state.run == crucible.max_run => vec![d.turn_left(), d.turn_right()],
+        Some(d) => vec![d, d.turn_left(), d.turn_right()],
+    };
+    directions
+        .into_iter()
+        .filter_map(|d| {
+            let position = d.step(state.position);
+            let run = match state.direction == Some(d) {
+                true => state.run + 1,
+                false => 1,
+            };
+            city.get(position).map(|&heat_loss| {
+                (
+                    State {
+                        position,
+                        direction: Some(d),
+                        run,
+                    },
+                    heat_loss,
+                )
+            })
+        })
+        .collect()
+}
+
+fn least_heat_loss(city: &Grid<u32>, crucible: Crucible) -> u32 {
+    let goal = (city.width() as i32 - 1, city.height() as i32 - 1);
+    let start = State {
+        position: (0, 0),
+        direction: None,
+        run: 0,
+    };
+    astar(
+        start,
+        |state| successors(city, crucible, state),
+        |state| state.position.0.abs_diff(goal.0) + state.position.1.abs_diff(goal.1),
+        |state| state.position == goal && state.run >= crucible.min_run,
+    )
+    .map(|(_, cost)| cost)
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+    #[test]
+    fn test_day17_part1() {
+        assert_eq!(
+            least_heat_loss(&parse_city(EXAMPLE), Crucible::REGULAR),
+            102
+        );
+    }
+
+    #[test]
+    fn test_day17_part2() {
+        assert_eq!(least_heat_loss(&parse_city(EXAMPLE), Crucible::ULTRA), 94);
+        let unfortunate = "111111111111\n999999999991\n999999999991\n999999999991\n999999999991";
+        assert_eq!(
+            least_heat_loss(&parse_city(unfortunate), Crucible::ULTRA),
+            71
+        );
+    }
+}
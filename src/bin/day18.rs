@@ -0,0 +1,101 @@
+use advent_of_code::grid::Direction;
+use advent_of_code::polygon::{lattice_points, trace};
+use std::fs::read_to_string;
+
+fn main() {
+    let plan = parse_dig_plan(&read_to_string("./data/day18.txt").unwrap());
+    println!("{}", lagoon_size(plan.iter().map(|x| x.step)));
+    println!("{}", lagoon_size(plan.iter().map(|x| x.decoded_step())));
+}
+
+#[derive(Debug, PartialEq)]
+struct Instruction {
+    step: (Direction, i64),
+    color: String,
+}
+
+impl Instruction {
+    fn decoded_step(&self) -> (Direction, i64) {
+        let distance = i64::from_str_radix(&self.color[..5], 16).unwrap();
+        let direction = match &self.color[5..] {
+            "0" => Direction::East,
+            "1" => Direction::South,
+            "2" => Direction::West,
+            "3" => Direction::North,
+            x => panic!("unknown direction code {}", x),
+        };
+        (direction, distance)
+    }
+}
+
+fn parse_instruction(line: &str) -> Instruction {
+    let mut splitted = line.split_whitespace();
+    let direction = match splitted.next().unwrap() {
+        "R" => Direction::East,
+        "D" => Direction::South,
+        "L" => Direction::West,
+        "U" => Direction::North,
+        x => panic!("unknown direction {}", x),
+    };
+    let distance = splitted.next().unwrap().parse::<i64>().unwrap();
+    let color = splitted
+        .next()
+        .unwrap()
+        .trim_matches(|x| x == '(' || x == ')' || x == '#')
+        .to_string();
+    Instruction {
+        step: (direction, distance),
+        color,
+    }
+}
+
+fn parse_dig_plan(lines: &str) -> Vec<Instruction> {
+    lines.lines().map(parse_instruction).collect()
+}
+
+fn lagoon_size(steps: impl IntoIterator<Item = (Direction, i64)>) -> i128 {
+    lattice_points(&trace(steps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    #[test]
+    fn test_parse_instruction() {
+        let instruction = parse_instruction("R 6 (#70c710)");
+        assert_eq!(
+            instruction,
+            Instruction {
+                step: (Direction::East, 6),
+                color: "70c710".to_string()
+            }
+        );
+        assert_eq!(instruction.decoded_step(), (Direction::East, 461937));
+    }
+
+    #[test]
+    fn test_day18() {
+        let plan = parse_dig_plan(EXAMPLE);
+        assert_eq!(lagoon_size(plan.iter().map(|x| x.step)), 62);
+        assert_eq!(
+            lagoon_size(plan.iter().map(|x| x.decoded_step())),
+            952408144115
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use advent_of_code::input::paragraphs;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+fn main() {
+    let (system, parts) = parse_input(&read_to_string("./data/day19.txt").unwrap());
+    println!(
+        "{}",
+        parts
+            .iter()
+            .filter(|part| system.accepts(part))
+            .map(|part| part.rating())
+            .sum::<u32>()
+    );
+    println!("{}", system.accepted_combinations(PartRange::full(1, 4000)));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    Greater,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    Accept,
+    Reject,
+    Workflow(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Condition {
+    category: Category,
+    comparison: Comparison,
+    value: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    condition: Option<Condition>,
+    target: Target,
+}
+
+advent_of_code::scan! {
+    #[derive(Debug, Default, Copy, Clone, PartialEq)]
+    struct Part = "{{x={x},m={m},a={a},s={s}}}" {
+        x: u32,
+        m: u32,
+        a: u32,
+        s: u32,
+    }
+}
+
+impl Part {
+    fn get(&self, category: Category) -> u32 {
+        match category {
+            Category::X => self.x,
+            Category::M => self.m,
+            Category::A => self.a,
+            Category::S => self.s,
+        }
+    }
+
+    fn rating(&self) -> u32 {
+        self.x + self.m + self.a + self.s
+    }
+}
+
+impl Condition {
+    fn matches(&self, part: &Part) -> bool {
+        match self.comparison {
+            Comparison::Less => part.get(self.category) < self.value,
+            Comparison::Greater => part.get(self.category) > self.value,
+        }
+    }
+}
+
+/// Half-open `start..end` ranges of ratings, one per category.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct PartRange {
+    x: (u32, u32),
+    m: (u32, u32),
+    a: (u32, u32),
+    s: (u32, u32),
+}
+
+impl PartRange {
+    fn full(min: u32, max: u32) -> Self {
+        Self {
+            x: (min, max + 1),
+            m: (min, max + 1),
+            a: (min, max + 1),
+            s: (min, max + 1),
+        }
+    }
+
+    fn get(&self, category: Category) -> (u32, u32) {
+        match category {
+            Category::X => self.x,
+            Category::M => self.m,
+            Category::A => self.a,
+            Category::S => self.s,
+        }
+    }
+
+    fn get_mut(&mut self, category: Category) -> &mut (u32, u32) {
+        match category {
+            Category::X => &mut self.x,
+            Category::M => &mut self.m,
+            Category::A => &mut self.a,
+            Category::S => &mut self.s,
+        }
+    }
+
+    fn combinations(&self) -> u64 {
+        [self.x, self.m, self.a, self.s]
+            .iter()
+            .map(|(start, end)| end.saturating_sub(*start) as u64)
+            .product()
+    }
+
+    /// Splits into the parts matching `condition` and the rest, dropping empty halves.
+    fn split(self, condition: &Condition) -> (Option<PartRange>, Option<PartRange>) {
+        let (start, end) = self.get(condition.category);
+        let boundary = match condition.comparison {
+            Comparison::Less => condition.value,
+            Comparison::Greater => condition.value + 1,
+        };
+        let boundary = boundary.clamp(start, end);
+        let (mut lower, mut upper) = (self, self);
+        *lower.get_mut(condition.category) = (start, boundary);
+        *upper.get_mut(condition.category) = (boundary, end);
+        let non_empty = |x: PartRange| (x.combinations() > 0).then_some(x);
+        match condition.comparison {
+            Comparison::Less => (non_empty(lower), non_empty(upper)),
+            Comparison::Greater => (non_empty(upper), non_empty(lower)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct System {
+    workflows: HashMap<String, Vec<Rule>>,
+}
+
+impl System {
+    fn accepts(&self, part: &Part) -> bool {
+        let mut current = "in";
+        loop {
+            let rule = self.workflows[current]
+                .iter()
+                .find(|rule| rule.condition.is_none_or(|x| x.matches(part)))
+                .unwrap();
+            match &rule.target {
+                Target::Accept => return true,
+                Target::Reject => return false,
+                Target::Workflow(name) => current = name,
+            }
+        }
+    }
+
+    fn accepted_combinations(&self, range: PartRange) -> u64 {
+        self.count_accepted(&Target::Workflow("in".to_string()), range)
+    }
+
+    fn count_accepted(&self, target: &Target, range: PartRange) -> u64 {
+        let name = match target {
+            Target::Accept => return range.combinations(),
+            Target::Reject => return 0,
+            Target::Workflow(name) => name,
+        };
+        let mut total = 0;
+        let mut remaining = Some(range);
+        for rule in &self.workflows[name] {
+            let Some(current) = remaining else {
+                break;
+            };
+            let (matched, rest) = match &rule.condition {
+                Some(condition) => current.split(condition),
+                None => (Some(current), None),
+            };
+            if let Some(matched) = matched {
+                total += self.count_accepted(&rule.target, matched);
+            }
+            remaining = rest;
+        }
+        total
+    }
+}
+
+fn parse_category(name: &str) -> Category {
+    match name {
+        "x" => Category::X,
+        "m" => Category::M,
+        "a" => Category::A,
+        "s" => Category::S,
+        _ => panic!("unknown category {}", name),
+    }
+}
+
+fn parse_target(name: &str) -> Target {
+    match name {
+        "A" => Target::Accept,
+        "R" => Target::Reject,
+        _ => Target::Workflow(name.to_string()),
+    }
+}
+
+fn parse_rule(raw_rule: &str) -> Rule {
+    let Some((condition, target)) = raw_rule.split_once(':') else {
+        return Rule {
+            condition: None,
+            target: parse_target(raw_rule),
+        };
+    };
+    let position = condition.find(['<', '>']).unwrap();
+    let comparison = match &condition[position..position + 1] {
+        "<" => Comparison::Less,
+        _ => Comparison::Greater,
+    };
+    Rule {
+        condition: Some(Condition {
+            category: parse_category(&condition[..position]),
+            comparison,
+            value: condition[position + 1..].parse::<u32>().unwrap(),
+        }),
+        target: parse_target(target),
+    }
+}
+
+fn parse_workflow(line: &str) -> (String, Vec<Rule>) {
+    let (name, rules) = line.trim_end_matches('}').split_once('{').unwrap();
+    (name.to_string(), rules.split(',').map(parse_rule).collect())
+}
+
+fn parse_input(text: &str) -> (System, Vec<Part>) {
+    let mut blocks = paragraphs(text);
+    let workflows = blocks.next().unwrap().lines().map(parse_workflow).collect();
+    let parts = blocks
+        .next()
+        .unwrap()
+        .lines()
+        .map(|line| line.parse().unwrap())
+        .collect();
+    (System { workflows }, parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+";
+
+    #[test]
+    fn test_parse_workflow() {
+        assert_eq!(
+            parse_workflow("pv{a>1716:R,A}"),
+            (
+                "pv".to_string(),
+                vec![
+                    Rule {
+                        condition: Some(Condition {
+                            category: Category::A,
+                            comparison: Comparison::Greater,
+                            value: 1716
+                        }),
+                        target: Target::Reject
+                    },
+                    Rule {
+                        condition: None,
+                        target: Target::Accept
+                    }
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_split_range() {
+        let range = PartRange::full(1, 10);
+        let condition = Condition {
+            category: Category::M,
+            comparison: Comparison::Greater,
+            value: 7,
+        };
+        let (matched, rest) = range.split(&condition);
+        assert_eq!(matched.unwrap().m, (8, 11));
+        assert_eq!(rest.unwrap().m, (1, 8));
+        let condition = Condition {
+            value: 10,
+            ..condition
+        };
+        assert_eq!(range.split(&condition), (None, Some(range)));
+    }
+
+    #[test]
+    fn test_day19() {
+        let (system, parts) = parse_input(EXAMPLE);
+        assert_eq!(
+            parts
+                .iter()
+                .filter(|part| system.accepts(part))
+                .map(|part| part.rating())
+                .sum::<u32>(),
+            19114
+        );
+        assert_eq!(
+            system.accepted_combinations(PartRange::full(1, 4000)),
+            167409079868000
+        );
+    }
+}
@@ -1,8 +1,11 @@
-use std::collections::HashMap;
+use advent_of_code::input::{try_ints, ParseError};
+use advent_of_code::intern::Interner;
 use std::fs::File;
 use std::io::{self, BufRead};
 use std::ops::Add;
 use std::path::Path;
+use std::str::FromStr;
+use std::sync::OnceLock;
 
 fn main() {
     let reference = Subset {
@@ -15,7 +18,7 @@ fn main() {
             "{}",
             lines
                 .into_iter()
-                .map(|line| parse_game_info(line.unwrap().as_str()))
+                .map(|line| line.unwrap().parse::<GameResult>().unwrap())
                 .filter(|result| result
                     .subsets
                     .clone()
@@ -30,7 +33,7 @@ fn main() {
             "{}",
             lines
                 .into_iter()
-                .map(|line| parse_game_info(line.unwrap().as_str()))
+                .map(|line| line.unwrap().parse::<GameResult>().unwrap())
                 .map(|x| x.sets_power())
                 .sum::<u32>()
         );
@@ -45,10 +48,16 @@ where
     Ok(io::BufReader::new(file).lines())
 }
 
-#[derive(Debug, PartialOrd, PartialEq)]
-struct GameResult {
-    id: u32,
-    subsets: Vec<Subset>,
+advent_of_code::scan! {
+    #[derive(Debug, PartialOrd, PartialEq)]
+    struct GameResult = "Game {id}: {subsets}" {
+        id: u32,
+        subsets: Vec<Subset> => parse_subsets,
+    }
+}
+
+fn parse_subsets(text: &str) -> Result<Vec<Subset>, ParseError> {
+    text.split(';').map(str::parse).collect()
 }
 
 impl GameResult {
@@ -66,29 +75,41 @@ struct Subset {
     blue: u32,
 }
 
-impl Subset {
-    fn new(raw_string: &str) -> Self {
-        let mut temp_table = HashMap::new();
-        for subpart in raw_string.split(',') {
-            let mut splitted = subpart.split_whitespace();
-            let (amount, color) = (
-                splitted.next().unwrap().parse::<u32>().unwrap(),
-                splitted.next().unwrap(),
-            );
-            temp_table.insert(color, amount);
-        }
-        Self {
-            red: *temp_table.get("red").unwrap_or(&0),
-            green: *temp_table.get("green").unwrap_or(&0),
-            blue: *temp_table.get("blue").unwrap_or(&0),
-        }
-    }
+/// Colour names interned in the order of the `Subset` fields.
+fn colours() -> &'static Interner {
+    static COLOURS: OnceLock<Interner> = OnceLock::new();
+    COLOURS.get_or_init(|| ["red", "green", "blue"].into_iter().collect())
+}
 
+impl Subset {
     fn is_possible_to_take(self, other: &Subset) -> bool {
         other.red <= self.red && other.green <= self.green && other.blue <= self.blue
     }
 }
 
+impl FromStr for Subset {
+    type Err = ParseError;
+
+    fn from_str(raw_string: &str) -> Result<Self, Self::Err> {
+        let mut amounts = [0; 3];
+        for subpart in raw_string.split(',') {
+            let amount = try_ints::<u32>(subpart)
+                .next()
+                .ok_or_else(|| ParseError::new(subpart, "missing amount"))??;
+            let color = subpart
+                .split_whitespace()
+                .nth(1)
+                .ok_or_else(|| ParseError::new(subpart, "missing colour"))?;
+            let id = colours()
+                .get(color)
+                .ok_or_else(|| ParseError::new(subpart, format!("unknown colour {}", color)))?;
+            amounts[id as usize] = amount;
+        }
+        let [red, green, blue] = amounts;
+        Ok(Self { red, green, blue })
+    }
+}
+
 impl Add for Subset {
     type Output = Self;
 
@@ -101,22 +122,6 @@ impl Add for Subset {
     }
 }
 
-fn parse_game_info(line: &str) -> GameResult {
-    let mut splitted = line.split(':');
-    let (game_part, cubes_part) = (splitted.next().unwrap(), splitted.next().unwrap());
-    let game_id = game_part
-        .split_whitespace()
-        .nth(1)
-        .unwrap()
-        .parse::<u32>()
-        .unwrap();
-    let subsets = cubes_part.split(';').map(Subset::new).collect();
-    GameResult {
-        id: game_id,
-        subsets,
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,8 +129,8 @@ mod tests {
     #[test]
     fn test_parse_game_info() {
         assert_eq!(
-            parse_game_info("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
-            GameResult {
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".parse::<GameResult>(),
+            Ok(GameResult {
                 id: 1,
                 subsets: Vec::from([
                     Subset {
@@ -144,11 +149,12 @@ mod tests {
                         red: 0
                     }
                 ])
-            }
+            })
         );
         assert_eq!(
-            parse_game_info("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"),
-            GameResult {
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"
+                .parse::<GameResult>(),
+            Ok(GameResult {
                 id: 2,
                 subsets: Vec::from([
                     Subset {
@@ -167,13 +173,12 @@ mod tests {
                         red: 0
                     }
                 ])
-            }
+            })
         );
         assert_eq!(
-            parse_game_info(
-                "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
-            ),
-            GameResult {
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red"
+                .parse::<GameResult>(),
+            Ok(GameResult {
                 id: 3,
                 subsets: Vec::from([
                     Subset {
@@ -192,8 +197,16 @@ mod tests {
                         blue: 0
                     }
                 ])
-            }
+            })
+        );
+        assert_eq!(
+            "Game 4: 3 purple"
+                .parse::<GameResult>()
+                .unwrap_err()
+                .reason(),
+            "unknown colour purple"
         );
+        assert!("Game four: 3 blue".parse::<GameResult>().is_err());
     }
 
     #[test]
@@ -208,7 +221,7 @@ mod tests {
             green: 26,
             blue: 11,
         };
-        assert_eq!(reference.is_possible_to_take(&overflow), false);
+        assert!(!reference.is_possible_to_take(&overflow));
     }
 
     #[test]
@@ -0,0 +1,366 @@
+use advent_of_code::intern::Interner;
+use advent_of_code::math::lcm_all;
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::fmt::Debug;
+use std::fs::read_to_string;
+
+fn main() {
+    let file = read_to_string("./data/day20.txt").unwrap();
+    let args = env::args().collect::<Vec<String>>();
+    if args.iter().any(|x| x == "--dot") {
+        print!("{}", parse_network(&file).to_dot());
+    }
+    if args.iter().any(|x| x == "--trace") {
+        let mut network = parse_network(&file);
+        let mut events = Vec::new();
+        network.push_button(|event| events.push(*event));
+        for event in events {
+            println!("{}", network.format_event(&event));
+        }
+    }
+    println!("{}", pulse_product(&mut parse_network(&file), 1000));
+    match presses_until_rx_low(&mut parse_network(&file)) {
+        Some(presses) => println!("{}", presses),
+        None => println!(
+            "rx isn't fed by counters cycling within {} presses",
+            MAX_PRESSES
+        ),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pulse {
+    Low,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PulseEvent {
+    from: usize,
+    to: usize,
+    pulse: Pulse,
+}
+
+trait Module: Debug {
+    fn connect_input(&mut self, _from: usize) {}
+
+    /// Handles an incoming pulse, returning the pulse to send to every output, if any.
+    fn receive(&mut self, from: usize, pulse: Pulse) -> Option<Pulse>;
+
+    fn dot_shape(&self) -> &'static str;
+}
+
+#[derive(Debug, Default)]
+struct Broadcaster;
+
+impl Module for Broadcaster {
+    fn receive(&mut self, _from: usize, pulse: Pulse) -> Option<Pulse> {
+        Some(pulse)
+    }
+
+    fn dot_shape(&self) -> &'static str {
+        "doublecircle"
+    }
+}
+
+#[derive(Debug, Default)]
+struct FlipFlop {
+    on: bool,
+}
+
+impl Module for FlipFlop {
+    fn receive(&mut self, _from: usize, pulse: Pulse) -> Option<Pulse> {
+        if pulse == Pulse::High {
+            return None;
+        }
+        self.on = !self.on;
+        match self.on {
+            true => Some(Pulse::High),
+            false => Some(Pulse::Low),
+        }
+    }
+
+    fn dot_shape(&self) -> &'static str {
+        "box"
+    }
+}
+
+#[derive(Debug, Default)]
+struct Conjunction {
+    memory: HashMap<usize, Pulse>,
+}
+
+impl Module for Conjunction {
+    fn connect_input(&mut self, from: usize) {
+        self.memory.insert(from, Pulse::Low);
+    }
+
+    fn receive(&mut self, from: usize, pulse: Pulse) -> Option<Pulse> {
+        self.memory.insert(from, pulse);
+        match self.memory.values().all(|x| *x == Pulse::High) {
+            true => Some(Pulse::Low),
+            false => Some(Pulse::High),
+        }
+    }
+
+    fn dot_shape(&self) -> &'static str {
+        "diamond"
+    }
+}
+
+/// Untyped modules such as `output` or `rx` which only receive pulses.
+#[derive(Debug, Default)]
+struct Sink;
+
+impl Module for Sink {
+    fn receive(&mut self, _from: usize, _pulse: Pulse) -> Option<Pulse> {
+        None
+    }
+
+    fn dot_shape(&self) -> &'static str {
+        "ellipse"
+    }
+}
+
+#[derive(Debug)]
+struct Network {
+    names: Interner,
+    modules: Vec<Box<dyn Module>>,
+    outputs: Vec<Vec<usize>>,
+}
+
+impl Network {
+    /// Id of the module called `name`, registering it as a sink if it wasn't seen yet.
+    fn intern(&mut self, name: &str) -> usize {
+        let id = self.names.intern(name) as usize;
+        if id == self.modules.len() {
+            self.modules.push(Box::new(Sink));
+            self.outputs.push(Vec::new());
+        }
+        id
+    }
+
+    fn id(&self, name: &str) -> Option<usize> {
+        self.names.get(name).map(|x| x as usize)
+    }
+
+    fn inputs_of(&self, module: usize) -> Vec<usize> {
+        (0..self.modules.len())
+            .filter(|&i| self.outputs[i].contains(&module))
+            .collect()
+    }
+
+    /// Sends a low pulse to the broadcaster and processes pulses in FIFO order until the
+    /// network settles, reporting every delivered pulse to `on_pulse`.
+    fn push_button(&mut self, mut on_pulse: impl FnMut(&PulseEvent)) {
+        let broadcaster = self.id("broadcaster").expect("network has no broadcaster");
+        let mut queue = VecDeque::from([PulseEvent {
+            from: broadcaster,
+            to: broadcaster,
+            pulse: Pulse::Low,
+        }]);
+        while let Some(event) = queue.pop_front() {
+            on_pulse(&event);
+            if let Some(pulse) = self.modules[event.to].receive(event.from, event.pulse) {
+                for &to in &self.outputs[event.to] {
+                    queue.push_back(PulseEvent {
+                        from: event.to,
+                        to,
+                        pulse,
+                    });
+                }
+            }
+        }
+    }
+
+    fn name(&self, id: usize) -> &str {
+        self.names.resolve(id as u32)
+    }
+
+    fn format_event(&self, event: &PulseEvent) -> String {
+        let pulse = match event.pulse {
+            Pulse::Low => "low",
+            Pulse::High => "high",
+        };
+        match event.from == event.to {
+            true => format!("button -{}-> {}", pulse, self.name(event.to)),
+            false => format!(
+                "{} -{}-> {}",
+                self.name(event.from),
+                pulse,
+                self.name(event.to)
+            ),
+        }
+    }
+
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n");
+        for (i, module) in self.modules.iter().enumerate() {
+            dot += &format!("    {} [shape={}];\n", self.name(i), module.dot_shape());
+        }
+        for (i, outputs) in self.outputs.iter().enumerate() {
+            for &to in outputs {
+                dot += &format!("    {} -> {};\n", self.name(i), self.name(to));
+            }
+        }
+        dot + "}\n"
+    }
+}
+
+fn parse_network(lines: &str) -> Network {
+    let mut network = Network {
+        names: Interner::new(),
+        modules: Vec::new(),
+        outputs: Vec::new(),
+    };
+    for line in lines.lines().filter(|x| !x.trim().is_empty()) {
+        let (module_part, outputs_part) = line.split_once("->").unwrap();
+        let module_part = module_part.trim();
+        let (module, name): (Box<dyn Module>, &str) = match module_part.split_at(1) {
+            ("%", name) => (Box::new(FlipFlop::default()), name),
+            ("&", name) => (Box::new(Conjunction::default()), name),
+            _ => (Box::new(Broadcaster), module_part),
+        };
+        let id = network.intern(name);
+        network.modules[id] = module;
+        network.outputs[id] = outputs_part
+            .split(',')
+            .map(|x| network.intern(x.trim()))
+            .collect();
+    }
+    for from in 0..network.modules.len() {
+        for to in network.outputs[from].clone() {
+            network.modules[to].connect_input(from);
+        }
+    }
+    network
+}
+
+fn pulse_product(network: &mut Network, presses: usize) -> u64 {
+    let (mut low, mut high) = (0, 0);
+    for _ in 0..presses {
+        network.push_button(|event| match event.pulse {
+            Pulse::Low => low += 1,
+            Pulse::High => high += 1,
+        });
+    }
+    low * high
+}
+
+/// Upper bound on button presses when waiting for counters to line up.
+const MAX_PRESSES: u64 = 1 << 16;
+
+/// Presses after which each input of `module` first sends it a high pulse, `None` if some
+/// input doesn't within `max_presses`.
+fn first_high_presses(
+    network: &mut Network,
+    module: usize,
+    max_presses: u64,
+) -> Option<HashMap<usize, u64>> {
+    let inputs = network.inputs_of(module);
+    let mut first_high = HashMap::new();
+    for presses in 1..=max_presses {
+        if first_high.len() == inputs.len() {
+            break;
+        }
+        network.push_button(|event| {
+            if event.to == module && event.pulse == Pulse::High {
+                first_high.entry(event.from).or_insert(presses);
+            }
+        });
+    }
+    (first_high.len() == inputs.len()).then_some(first_high)
+}
+
+/// `rx` is fed by a single conjunction whose inputs are independent counters, so `rx` gets
+/// a low pulse once all of them line up, at the least common multiple of their cycles.
+/// `None` if the network isn't shaped like that.
+fn presses_until_rx_low(network: &mut Network) -> Option<u64> {
+    let feeders = network.inputs_of(network.id("rx")?);
+    let [feeder] = feeders[..] else {
+        return None;
+    };
+    let cycles = first_high_presses(network, feeder, MAX_PRESSES)?
+        .into_values()
+        .collect::<Vec<u64>>();
+    (!cycles.is_empty()).then(|| lcm_all(&cycles))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIMPLE: &str = "broadcaster -> a, b, c\n%a -> b\n%b -> c\n%c -> inv\n&inv -> a";
+    const INTERESTING: &str =
+        "broadcaster -> a\n%a -> inv, con\n&inv -> b\n%b -> con\n&con -> output";
+    const COUNTERS: &str = "broadcaster -> a1, b1
+%a1 -> a2, ca
+%a2 -> ca
+&ca -> ia
+&ia -> feed
+%b1 -> b2, cb
+%b2 -> b3, cb
+%b3 -> cb
+&cb -> ib
+&ib -> feed
+&feed -> rx";
+
+    #[test]
+    fn test_push_button_trace() {
+        let mut network = parse_network(INTERESTING);
+        let mut trace = Vec::new();
+        network.push_button(|event| trace.push(*event));
+        assert_eq!(
+            trace
+                .iter()
+                .map(|x| network.format_event(x))
+                .collect::<Vec<String>>(),
+            vec![
+                "button -low-> broadcaster",
+                "broadcaster -low-> a",
+                "a -high-> inv",
+                "a -high-> con",
+                "inv -low-> b",
+                "con -high-> output",
+                "b -high-> con",
+                "con -low-> output",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_day20_part1() {
+        assert_eq!(pulse_product(&mut parse_network(SIMPLE), 1000), 32000000);
+        assert_eq!(
+            pulse_product(&mut parse_network(INTERESTING), 1000),
+            11687500
+        );
+    }
+
+    #[test]
+    fn test_day20_part2() {
+        let mut network = parse_network(COUNTERS);
+        let feed = network.id("feed").unwrap();
+        let first_high = first_high_presses(&mut network, feed, 10).unwrap();
+        assert_eq!(first_high[&network.id("ia").unwrap()], 3);
+        assert_eq!(first_high[&network.id("ib").unwrap()], 7);
+        assert_eq!(
+            first_high_presses(&mut parse_network(COUNTERS), feed, 6),
+            None
+        );
+        assert_eq!(presses_until_rx_low(&mut parse_network(COUNTERS)), Some(21));
+        assert_eq!(presses_until_rx_low(&mut parse_network(SIMPLE)), None);
+        let stuck = "broadcaster -> a\n%a -> ia\n&ia -> feed\n&feed -> rx\n&never -> feed";
+        assert_eq!(presses_until_rx_low(&mut parse_network(stuck)), None);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = parse_network(SIMPLE).to_dot();
+        assert!(dot.starts_with("digraph network {\n    broadcaster [shape=doublecircle];\n"));
+        assert!(dot.contains("    inv [shape=diamond];\n"));
+        assert!(dot.contains("    c -> inv;\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+}
@@ -0,0 +1,94 @@
+use advent_of_code::grid::{Grid, Position};
+use advent_of_code::search::bfs_distances_within;
+use advent_of_code::sequence::DifferenceTable;
+use std::fs::read_to_string;
+
+fn main() {
+    let (garden, start) = parse_garden(&read_to_string("./data/day21.txt").unwrap());
+    println!("{}", reachable_plots(&garden, start, 64, false));
+    println!("{}", extrapolate_reachable_plots(&garden, start, 26501365));
+}
+
+fn parse_garden(lines: &str) -> (Grid<bool>, Position) {
+    let tiles = Grid::parse_with(lines, |x| x);
+    let start = tiles.find(|&x| x == 'S').unwrap();
+    (tiles.map(|&x| x != '#'), start)
+}
+
+/// Plots reachable in exactly `steps` steps, on the garden repeated infinitely if `infinite`.
+fn reachable_plots(garden: &Grid<bool>, start: Position, steps: usize, infinite: bool) -> usize {
+    let successors = |position: &Position| -> Vec<Position> {
+        match infinite {
+            true => garden
+                .wrapping()
+                .neighbours(*position)
+                .filter(|(_, p)| garden.wrapping()[*p])
+                .map(|(_, p)| p)
+                .collect(),
+            false => garden
+                .neighbours(*position)
+                .filter(|(_, p)| garden[*p])
+                .map(|(_, p)| p)
+                .collect(),
+        }
+    };
+    // A plot reached in `d` steps can be revisited every other step afterwards.
+    bfs_distances_within(start, successors, steps)
+        .values()
+        .filter(|&&d| d % 2 == steps % 2)
+        .count()
+}
+
+/// The garden's borders and the start's row and column are free of rocks, so the reachable
+/// area grows by whole garden copies every `width` steps and the count at `offset + k * width`
+/// steps is a quadratic in `k`, fitted from its first three values.
+fn extrapolate_reachable_plots(garden: &Grid<bool>, start: Position, steps: usize) -> i64 {
+    let period = garden.width();
+    let offset = steps % period;
+    let samples = (0..3)
+        .map(|k| reachable_plots(garden, start, offset + k * period, true) as i64)
+        .collect::<Vec<i64>>();
+    DifferenceTable::new(samples).value_at((steps / period) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+    #[test]
+    fn test_day21_part1() {
+        let (garden, start) = parse_garden(EXAMPLE);
+        assert_eq!(reachable_plots(&garden, start, 6, false), 16);
+    }
+
+    #[test]
+    fn test_infinite_garden() {
+        let (garden, start) = parse_garden(EXAMPLE);
+        assert_eq!(reachable_plots(&garden, start, 6, true), 16);
+        assert_eq!(reachable_plots(&garden, start, 10, true), 50);
+        assert_eq!(reachable_plots(&garden, start, 50, true), 1594);
+        assert_eq!(reachable_plots(&garden, start, 100, true), 6536);
+    }
+
+    #[test]
+    fn test_extrapolate_reachable_plots() {
+        let (garden, start) = parse_garden(".....\n.....\n..S..\n.....\n.....");
+        assert_eq!(reachable_plots(&garden, start, 12, true), 13 * 13);
+        assert_eq!(
+            extrapolate_reachable_plots(&garden, start, 1002),
+            1003 * 1003
+        );
+    }
+}
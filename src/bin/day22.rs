@@ -0,0 +1,173 @@
+use advent_of_code::space::{Cuboid, Point3};
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+fn main() {
+    let bricks = settle(parse_bricks(&read_to_string("./data/day22.txt").unwrap()));
+    let graph = SupportGraph::new(&bricks);
+    let falling = graph.chain_reaction_counts();
+    println!("{}", falling.iter().filter(|&&x| x == 0).count());
+    println!("{}", falling.iter().sum::<usize>());
+}
+
+fn parse_point(raw_point: &str) -> Point3 {
+    let coordinates = raw_point
+        .split(',')
+        .map(|x| x.trim().parse::<i64>().unwrap())
+        .collect::<Vec<i64>>();
+    Point3::new(coordinates[0], coordinates[1], coordinates[2])
+}
+
+fn parse_bricks(lines: &str) -> Vec<Cuboid> {
+    lines
+        .lines()
+        .filter(|x| !x.trim().is_empty())
+        .map(|line| {
+            let (a, b) = line.split_once('~').unwrap();
+            Cuboid::new(parse_point(a), parse_point(b))
+        })
+        .collect()
+}
+
+/// Drops every brick as far as it goes, returning them ordered by their bottom, so each brick
+/// comes after all of the bricks supporting it.
+fn settle(mut bricks: Vec<Cuboid>) -> Vec<Cuboid> {
+    bricks.sort_by_key(|x| x.min.z);
+    let mut heights = HashMap::new();
+    for brick in bricks.iter_mut() {
+        let floor = brick
+            .footprint()
+            .map(|column| *heights.get(&column).unwrap_or(&0))
+            .max()
+            .unwrap();
+        *brick = brick.translate(Point3::new(0, 0, floor + 1 - brick.min.z));
+        for column in brick.footprint() {
+            heights.insert(column, brick.max.z);
+        }
+    }
+    bricks.sort_by_key(|x| x.min.z);
+    bricks
+}
+
+#[derive(Debug, PartialEq)]
+struct SupportGraph {
+    /// Bricks each brick rests on, empty for bricks lying on the ground.
+    supporters: Vec<Vec<usize>>,
+    /// Bricks resting on each brick.
+    supported: Vec<Vec<usize>>,
+}
+
+impl SupportGraph {
+    fn new(settled: &[Cuboid]) -> Self {
+        let mut top: HashMap<(i64, i64), (i64, usize)> = HashMap::new();
+        let mut supporters = vec![Vec::new(); settled.len()];
+        let mut supported = vec![Vec::new(); settled.len()];
+        for (i, brick) in settled.iter().enumerate() {
+            for column in brick.footprint() {
+                if let Some(&(z, below)) = top.get(&column) {
+                    if z + 1 == brick.min.z && !supporters[i].contains(&below) {
+                        supporters[i].push(below);
+                        supported[below].push(i);
+                    }
+                }
+            }
+            for column in brick.footprint() {
+                top.insert(column, (brick.max.z, i));
+            }
+        }
+        Self {
+            supporters,
+            supported,
+        }
+    }
+
+    /// Number of other bricks that fall when each brick is disintegrated.
+    ///
+    /// A brick falls exactly when the removed one dominates it in the support graph rooted at
+    /// the ground, so the answer is the size of its subtree in the dominator tree. Bricks are
+    /// in topological order, which lets each immediate dominator be found as the lowest common
+    /// ancestor of the brick's supporters.
+    fn chain_reaction_counts(&self) -> Vec<usize> {
+        let n = self.supporters.len();
+        let ground = n;
+        let mut dominator = vec![ground; n + 1];
+        let mut depth = vec![0; n + 1];
+        for i in 0..n {
+            let mut supporters = self.supporters[i].iter().copied();
+            let Some(first) = supporters.next() else {
+                depth[i] = 1;
+                continue;
+            };
+            let common = supporters.fold(first, |a, b| {
+                let (mut a, mut b) = (a, b);
+                while a != b {
+                    if depth[a] >= depth[b] {
+                        a = dominator[a];
+                    } else {
+                        b = dominator[b];
+                    }
+                }
+                a
+            });
+            dominator[i] = common;
+            depth[i] = depth[common] + 1;
+        }
+        let mut subtree = vec![1; n + 1];
+        for i in (0..n).rev() {
+            subtree[dominator[i]] += subtree[i];
+        }
+        subtree[..n].iter().map(|x| x - 1).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9";
+
+    fn simulate_chain_reaction(graph: &SupportGraph, removed: usize) -> usize {
+        let mut fallen = vec![false; graph.supporters.len()];
+        fallen[removed] = true;
+        let mut count = 0;
+        for i in removed + 1..graph.supporters.len() {
+            if !graph.supporters[i].is_empty() && graph.supporters[i].iter().all(|&x| fallen[x]) {
+                fallen[i] = true;
+                count += 1;
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn test_support_graph() {
+        let graph = SupportGraph::new(&settle(parse_bricks(EXAMPLE)));
+        assert_eq!(graph.supporters[0], Vec::<usize>::new());
+        assert_eq!(graph.supported[0], vec![1, 2]);
+        assert_eq!(graph.supporters[3], vec![1, 2]);
+        assert_eq!(graph.supporters[6], vec![5]);
+    }
+
+    #[test]
+    fn test_chain_reaction_matches_simulation() {
+        let graph = SupportGraph::new(&settle(parse_bricks(EXAMPLE)));
+        let counts = graph.chain_reaction_counts();
+        for (i, count) in counts.into_iter().enumerate() {
+            assert_eq!(count, simulate_chain_reaction(&graph, i));
+        }
+    }
+
+    #[test]
+    fn test_day22() {
+        let graph = SupportGraph::new(&settle(parse_bricks(EXAMPLE)));
+        let falling = graph.chain_reaction_counts();
+        assert_eq!(falling.iter().filter(|&&x| x == 0).count(), 5);
+        assert_eq!(falling.iter().sum::<usize>(), 7);
+    }
+}
@@ -0,0 +1,178 @@
+use advent_of_code::grid::{Direction, Grid, Position};
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+fn main() {
+    let trails = parse_trails(&read_to_string("./data/day23.txt").unwrap());
+    println!("{}", TrailGraph::compress(&trails, true).longest_hike());
+    println!("{}", TrailGraph::compress(&trails, false).longest_hike());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tile {
+    Path,
+    Forest,
+    Slope(Direction),
+}
+
+fn parse_trails(lines: &str) -> Grid<Tile> {
+    Grid::parse_with(lines, |x| match x {
+        '#' => Tile::Forest,
+        '^' => Tile::Slope(Direction::North),
+        '>' => Tile::Slope(Direction::East),
+        'v' => Tile::Slope(Direction::South),
+        '<' => Tile::Slope(Direction::West),
+        _ => Tile::Path,
+    })
+}
+
+fn moves(trails: &Grid<Tile>, position: Position, slippery: bool) -> Vec<Position> {
+    trails
+        .neighbours(position)
+        .filter(|(_, p)| trails[*p] != Tile::Forest)
+        .filter(|(d, _)| match (slippery, trails[position]) {
+            (true, Tile::Slope(slope)) => slope == *d,
+            _ => true,
+        })
+        .map(|(_, p)| p)
+        .collect()
+}
+
+/// Junctions of the trail map joined by the corridors between them, weighted by length.
+#[derive(Debug)]
+struct TrailGraph {
+    edges: Vec<Vec<(usize, u32)>>,
+    start: usize,
+    end: usize,
+}
+
+impl TrailGraph {
+    fn compress(trails: &Grid<Tile>, slippery: bool) -> Self {
+        let row = |y: usize| {
+            (0..trails.width() as i32)
+                .map(move |x| (x, y as i32))
+                .find(|p| trails[*p] == Tile::Path)
+                .unwrap()
+        };
+        let (start, end) = (row(0), row(trails.height() - 1));
+        let junctions = trails
+            .positions()
+            .filter(|&p| {
+                p == start
+                    || p == end
+                    || (trails[p] != Tile::Forest && moves(trails, p, false).len() > 2)
+            })
+            .enumerate()
+            .map(|(i, p)| (p, i))
+            .collect::<HashMap<Position, usize>>();
+        assert!(
+            junctions.len() <= 64,
+            "too many junctions for a u64 visited mask"
+        );
+        let mut edges = vec![Vec::new(); junctions.len()];
+        for (&junction, &from) in &junctions {
+            for first in moves(trails, junction, slippery) {
+                let (mut previous, mut current, mut length) = (junction, first, 1);
+                while !junctions.contains_key(&current) {
+                    let next = moves(trails, current, slippery)
+                        .into_iter()
+                        .find(|&p| p != previous);
+                    let Some(next) = next else {
+                        break;
+                    };
+                    (previous, current, length) = (current, next, length + 1);
+                }
+                if let Some(&to) = junctions.get(&current) {
+                    edges[from].push((to, length));
+                }
+            }
+        }
+        Self {
+            edges,
+            start: junctions[&start],
+            end: junctions[&end],
+        }
+    }
+
+    fn longest_hike(&self) -> u32 {
+        // The last junction before the end has to go straight to it, otherwise the end is cut off.
+        let before_end =
+            (0..self.edges.len()).find(|&i| self.edges[i].iter().any(|&(to, _)| to == self.end));
+        self.longest_from(self.start, 1 << self.start, before_end)
+            .unwrap()
+    }
+
+    fn longest_from(&self, node: usize, visited: u64, before_end: Option<usize>) -> Option<u32> {
+        if node == self.end {
+            return Some(0);
+        }
+        let mut best = None;
+        for &(next, length) in &self.edges[node] {
+            if visited & 1 << next != 0 || (Some(node) == before_end && next != self.end) {
+                continue;
+            }
+            if let Some(rest) = self.longest_from(next, visited | 1 << next, before_end) {
+                best = best.max(Some(length + rest));
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#";
+
+    #[test]
+    fn test_compress() {
+        let trails = parse_trails(EXAMPLE);
+        let graph = TrailGraph::compress(&trails, false);
+        assert_eq!(graph.edges.len(), 9);
+        assert_eq!(graph.edges[graph.start].len(), 1);
+        let slippery = TrailGraph::compress(&trails, true);
+        assert!(
+            slippery.edges.iter().map(Vec::len).sum::<usize>()
+                < graph.edges.iter().map(Vec::len).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_day23_part1() {
+        assert_eq!(
+            TrailGraph::compress(&parse_trails(EXAMPLE), true).longest_hike(),
+            94
+        );
+    }
+
+    #[test]
+    fn test_day23_part2() {
+        assert_eq!(
+            TrailGraph::compress(&parse_trails(EXAMPLE), false).longest_hike(),
+            154
+        );
+    }
+}
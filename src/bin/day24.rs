@@ -0,0 +1,211 @@
+use advent_of_code::linalg::{solve, Rational};
+use advent_of_code::space::Point3;
+use std::fs::read_to_string;
+
+fn main() {
+    let hailstones = parse_hailstones(&read_to_string("./data/day24.txt").unwrap());
+    println!(
+        "{}",
+        count_crossings(&hailstones, 200000000000000, 400000000000000)
+    );
+    let rock = throw_rock(&hailstones).unwrap();
+    println!("{}", rock.position.x + rock.position.y + rock.position.z);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Hailstone {
+    position: Point3,
+    velocity: Point3,
+}
+
+fn parse_point(raw_point: &str) -> Point3 {
+    let coordinates = raw_point
+        .split(',')
+        .map(|x| x.trim().parse::<i64>().unwrap())
+        .collect::<Vec<i64>>();
+    Point3::new(coordinates[0], coordinates[1], coordinates[2])
+}
+
+fn parse_hailstones(lines: &str) -> Vec<Hailstone> {
+    lines
+        .lines()
+        .filter(|x| !x.trim().is_empty())
+        .map(|line| {
+            let (position, velocity) = line.split_once('@').unwrap();
+            Hailstone {
+                position: parse_point(position),
+                velocity: parse_point(velocity),
+            }
+        })
+        .collect()
+}
+
+fn r(value: i64) -> Rational {
+    Rational::from(value)
+}
+
+/// Point where the future XY paths of two hailstones cross, ignoring time and the Z axis.
+fn path_crossing(a: &Hailstone, b: &Hailstone) -> Option<(Rational, Rational)> {
+    let (pa, va, pb, vb) = (a.position, a.velocity, b.position, b.velocity);
+    let times = solve(
+        vec![vec![r(va.x), r(-vb.x)], vec![r(va.y), r(-vb.y)]],
+        vec![r(pb.x - pa.x), r(pb.y - pa.y)],
+    )?;
+    if times.iter().any(|&t| t < Rational::ZERO) {
+        return None;
+    }
+    Some((r(pa.x) + times[0] * r(va.x), r(pa.y) + times[0] * r(va.y)))
+}
+
+fn count_crossings(hailstones: &[Hailstone], min: i64, max: i64) -> usize {
+    let area = r(min)..=r(max);
+    hailstones
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| hailstones[i + 1..].iter().map(move |b| (a, b)))
+        .filter_map(|(a, b)| path_crossing(a, b))
+        .filter(|(x, y)| area.contains(x) && area.contains(y))
+        .count()
+}
+
+type Vector = [i128; 3];
+
+fn vector(point: Point3) -> Vector {
+    [point.x as i128, point.y as i128, point.z as i128]
+}
+
+fn sub(a: Vector, b: Vector) -> Vector {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn dot(a: Vector, b: Vector) -> i128 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vector, b: Vector) -> Vector {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// Finds the rock that hits every hailstone, seen from the frame of the first hailstone.
+///
+/// There the rock passes through the origin, so it lies in the plane through the origin and
+/// the path of any other hailstone. The rock hits hailstone 1 where its path crosses the plane
+/// of hailstone 2 and vice versa, which gives two exact times and points on the rock's path.
+/// Setting up the full linear system instead overflows `i128` at puzzle input magnitudes.
+fn throw_rock(hailstones: &[Hailstone]) -> Option<Hailstone> {
+    let origin = &hailstones[0];
+    let relative = |h: &Hailstone| {
+        (
+            sub(vector(h.position), vector(origin.position)),
+            sub(vector(h.velocity), vector(origin.velocity)),
+        )
+    };
+    let hit_time = |(p, v): (Vector, Vector), normal: Vector| {
+        let speed = dot(v, normal);
+        (speed != 0).then(|| -Rational::new(dot(p, normal), speed))
+    };
+    hailstones[1..].windows(2).find_map(|pair| {
+        let (first, second) = (relative(&pair[0]), relative(&pair[1]));
+        let (t1, t2) = (
+            hit_time(first, cross(second.0, second.1))?.to_integer()?,
+            hit_time(second, cross(first.0, first.1))?.to_integer()?,
+        );
+        if t1 == t2 {
+            return None;
+        }
+        let hit = |h: &Hailstone, t: i128| {
+            let (p, v) = (vector(h.position), vector(h.velocity));
+            [p[0] + t * v[0], p[1] + t * v[1], p[2] + t * v[2]]
+        };
+        let (c1, c2) = (hit(&pair[0], t1), hit(&pair[1], t2));
+        let delta = sub(c2, c1);
+        if delta.iter().any(|x| x % (t2 - t1) != 0) {
+            return None;
+        }
+        let velocity = delta.map(|x| x / (t2 - t1));
+        let position = sub(c1, velocity.map(|x| x * t1));
+        let point = |v: Vector| Point3::new(v[0] as i64, v[1] as i64, v[2] as i64);
+        Some(Hailstone {
+            position: point(position),
+            velocity: point(velocity),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3";
+
+    #[test]
+    fn test_path_crossing() {
+        let hailstones = parse_hailstones(EXAMPLE);
+        assert_eq!(
+            path_crossing(&hailstones[0], &hailstones[1]),
+            Some((Rational::new(43, 3), Rational::new(46, 3)))
+        );
+        assert_eq!(path_crossing(&hailstones[1], &hailstones[2]), None);
+        assert_eq!(path_crossing(&hailstones[0], &hailstones[4]), None);
+    }
+
+    #[test]
+    fn test_day24() {
+        let hailstones = parse_hailstones(EXAMPLE);
+        assert_eq!(count_crossings(&hailstones, 7, 27), 2);
+        assert_eq!(
+            throw_rock(&hailstones),
+            Some(parse_hailstones("24, 13, 10 @ -3, 1, 2")[0])
+        );
+    }
+
+    #[test]
+    fn test_throw_rock_at_large_coordinates() {
+        let rock = Hailstone {
+            position: Point3::new(287430900705823, 451620998712421, 260730677041648),
+            velocity: Point3::new(-79, -219, 21),
+        };
+        let velocities = [
+            (214, -31, 83),
+            (-98, 72, 4),
+            (17, -250, -130),
+            (156, 8, -44),
+            (-40, 111, 97),
+            (63, -186, 12),
+        ];
+        let times = [
+            731210234113,
+            98112503224,
+            512008771235,
+            340441890117,
+            905550123671,
+            44120987335,
+        ];
+        let hailstones = velocities
+            .iter()
+            .zip(times)
+            .map(|(&(x, y, z), t)| {
+                let velocity = Point3::new(x, y, z);
+                let hit = rock.position
+                    + Point3::new(
+                        rock.velocity.x * t,
+                        rock.velocity.y * t,
+                        rock.velocity.z * t,
+                    );
+                Hailstone {
+                    position: hit - Point3::new(velocity.x * t, velocity.y * t, velocity.z * t),
+                    velocity,
+                }
+            })
+            .collect::<Vec<Hailstone>>();
+        assert_eq!(throw_rock(&hailstones), Some(rock));
+    }
+}
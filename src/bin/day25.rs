@@ -0,0 +1,65 @@
+use advent_of_code::graph::Graph;
+use std::fs::read_to_string;
+
+fn main() {
+    let graph = parse_wiring(&read_to_string("./data/day25.txt").unwrap());
+    println!("{}", group_product(&graph, 3).unwrap());
+}
+
+fn parse_wiring(lines: &str) -> Graph {
+    let mut graph = Graph::new();
+    for line in lines.lines().filter(|x| !x.trim().is_empty()) {
+        let (name, connected) = line.split_once(':').unwrap();
+        let component = graph.node(name.trim());
+        for other in connected.split_whitespace() {
+            let other = graph.node(other);
+            graph.add_edge(component, other, 1);
+        }
+    }
+    graph
+}
+
+/// Product of the group sizes after cutting `wires` wires, `None` if no such cut exists.
+fn group_product(graph: &Graph, wires: u64) -> Option<usize> {
+    let cut = graph.min_cut().filter(|x| x.weight == wires)?;
+    Some(cut.side.len() * (graph.len() - cut.side.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr";
+
+    #[test]
+    fn test_parse_wiring() {
+        let graph = parse_wiring(EXAMPLE);
+        assert_eq!(graph.len(), 15);
+        let jqt = graph.id("jqt").unwrap();
+        let mut connected = graph
+            .neighbours(jqt)
+            .map(|(x, _)| graph.name(x))
+            .collect::<Vec<_>>();
+        connected.sort();
+        assert_eq!(connected, vec!["ntq", "nvd", "rhn", "xhk"]);
+    }
+
+    #[test]
+    fn test_day25() {
+        let graph = parse_wiring(EXAMPLE);
+        assert_eq!(group_product(&graph, 3), Some(54));
+        assert_eq!(group_product(&graph, 2), None);
+    }
+}
@@ -1,4 +1,6 @@
+use advent_of_code::input::ParseError;
 use std::fs::read_to_string;
+use std::str::FromStr;
 
 fn main() {
     let (numbers, symbols) = parse_engine_schematic("./data/day3.txt");
@@ -20,42 +22,89 @@ struct Number {
     end: i32,
 }
 
+/// Reads a number token such as `467`, found at the start of row 0 until it's [`placed`].
+///
+/// [`placed`]: Number::placed
+impl FromStr for Number {
+    type Err = ParseError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        if token.is_empty() || !token.chars().all(|x| x.is_ascii_digit()) {
+            return Err(ParseError::new(token, "not a number"));
+        }
+        Ok(Number {
+            value: token.parse().map_err(|e| ParseError::new(token, e))?,
+            row: 0,
+            start: 0,
+            end: token.len() as i32 - 1,
+        })
+    }
+}
+
+impl Number {
+    fn placed(self, row: usize, column: usize) -> Self {
+        Number {
+            row: row as i32,
+            start: self.start + column as i32,
+            end: self.end + column as i32,
+            ..self
+        }
+    }
+}
+
+/// Reads a single symbol token such as `*`, found at the start of row 0 until it's [`placed`].
+///
+/// [`placed`]: Symbol::placed
+impl FromStr for Symbol {
+    type Err = ParseError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let mut chars = token.chars();
+        match (chars.next(), chars.next()) {
+            (Some(value), None) if value != '.' && !value.is_ascii_digit() => Ok(Symbol {
+                position: (0, 0),
+                value,
+            }),
+            _ => Err(ParseError::new(token, "not a symbol")),
+        }
+    }
+}
+
+impl Symbol {
+    fn placed(self, row: usize, column: usize) -> Self {
+        Symbol {
+            position: (self.position.0 + column as i32, row as i32),
+            ..self
+        }
+    }
+}
+
+/// Splits a line into runs of digits and single other characters, with their columns.
+fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 0;
+    std::iter::from_fn(move || {
+        let rest = &line[column..];
+        let first = rest.chars().next()?;
+        let length = match first.is_ascii_digit() {
+            true => rest
+                .find(|x: char| !x.is_ascii_digit())
+                .unwrap_or(rest.len()),
+            false => first.len_utf8(),
+        };
+        column += length;
+        Some((column - length, &rest[..length]))
+    })
+}
+
 fn parse_engine_schematic(filename: &str) -> (Vec<Number>, Vec<Symbol>) {
     let file_text = read_to_string(filename).unwrap();
-    let enum_lines = file_text.lines().enumerate();
     let mut numbers = Vec::new();
     let mut symbols = Vec::new();
-    for enum_line in enum_lines {
-        let mut enum_chars = enum_line.1.chars().enumerate();
-        while let Some(char) = enum_chars.next() {
-            if char.1.is_ascii_digit() {
-                let mut digits = String::from(char.1);
-                let start = char.0;
-                let mut end = char.0;
-                for ch in enum_chars.by_ref() {
-                    if ch.1 == '.' {
-                        break;
-                    } else if !ch.1.is_ascii_digit() {
-                        symbols.push(Symbol {
-                            position: (ch.0 as i32, enum_line.0 as i32),
-                            value: ch.1,
-                        });
-                        break;
-                    }
-                    digits.push(ch.1);
-                    end += 1;
-                }
-                numbers.push(Number {
-                    value: digits.parse::<u32>().unwrap(),
-                    row: enum_line.0 as i32,
-                    start: start as i32,
-                    end: end as i32,
-                });
-            } else if char.1 != '.' {
-                symbols.push(Symbol {
-                    position: (char.0 as i32, enum_line.0 as i32),
-                    value: char.1,
-                })
+    for (row, line) in file_text.lines().enumerate() {
+        for (column, token) in tokens(line).filter(|(_, x)| *x != ".") {
+            match token.starts_with(|x: char| x.is_ascii_digit()) {
+                true => numbers.push(token.parse::<Number>().unwrap().placed(row, column)),
+                false => symbols.push(token.parse::<Symbol>().unwrap().placed(row, column)),
             }
         }
     }
@@ -154,4 +203,31 @@ mod tests {
             Vec::from([467,])
         )
     }
+
+    #[test]
+    fn test_tokens_from_str() {
+        assert_eq!(
+            "467".parse::<Number>().map(|x| x.placed(2, 5)),
+            Ok(Number {
+                value: 467,
+                row: 2,
+                start: 5,
+                end: 7
+            })
+        );
+        assert!("..467..".parse::<Number>().is_err());
+        assert!("".parse::<Number>().is_err());
+        assert_eq!(
+            "*".parse::<Symbol>().map(|x| x.placed(1, 3)),
+            Ok(Symbol {
+                position: (3, 1),
+                value: '*'
+            })
+        );
+        assert_eq!("*$".parse::<Symbol>().unwrap_err().reason(), "not a symbol");
+        assert_eq!(
+            tokens("467..*12").collect::<Vec<_>>(),
+            vec![(0, "467"), (3, "."), (4, "."), (5, "*"), (6, "12")]
+        );
+    }
 }
@@ -1,41 +1,110 @@
+use advent_of_code::input::{ints_by_line, try_ints, ParseError};
+use advent_of_code::math::{checked_mul, Int};
 use std::fs::read_to_string;
+use std::str::FromStr;
 
 fn main() {
     let file = read_to_string("./data/day6.txt").unwrap();
     println!("{}", parse_race_info(&file).iter().map(|info| info.number_ways_to_beat()).reduce(|x, y| x * y).unwrap());
-    println!("{}", parse_race_info_with_bad_kerning(&file).number_ways_to_beat());
+    println!("{}", parse_race_info_with_bad_kerning(&file).unwrap().number_ways_to_beat());
 }
 
 #[derive(Debug, PartialEq)]
-struct RaceInfo {
-    time: u64,
-    distance: u64
+struct RaceInfo<T = u64> {
+    time: T,
+    distance: T
 }
 
-impl RaceInfo {
-    fn number_ways_to_beat(&self) -> u64 {
-        (0..self.time + 1).filter(|delay| (self.time - delay) * (delay) > self.distance).count() as u64
+impl<T: Int> RaceInfo<T> {
+    fn beats(&self, delay: T) -> bool {
+        // a product that overflows is further than any distance that fits
+        match checked_mul(self.time - delay, delay) {
+            Ok(travelled) => travelled > self.distance,
+            Err(_) => true,
+        }
+    }
+
+    /// The distance only grows with the delay up to half the time and is symmetric around it,
+    /// so a binary search for the shortest winning delay is enough.
+    fn number_ways_to_beat(&self) -> T {
+        let (mut low, mut high) = (T::ZERO, self.time / T::TWO);
+        if !self.beats(high) {
+            return T::ZERO;
+        }
+        while low < high {
+            let middle = low + (high - low) / T::TWO;
+            match self.beats(middle) {
+                true => high = middle,
+                false => low = middle + T::ONE,
+            }
+        }
+        self.time - low - low + T::ONE
+    }
+}
+
+/// A race read with bad kerning, in `u128` when it doesn't fit in `u64`.
+#[derive(Debug, PartialEq)]
+enum KernedRace {
+    Narrow(RaceInfo<u64>),
+    Wide(RaceInfo<u128>),
+}
+
+impl KernedRace {
+    fn number_ways_to_beat(&self) -> u128 {
+        match self {
+            KernedRace::Narrow(race) => race.number_ways_to_beat() as u128,
+            KernedRace::Wide(race) => race.number_ways_to_beat(),
+        }
+    }
+}
+
+/// Reads a sheet holding a single race, like the one with bad kerning.
+impl<T: Int + FromStr> FromStr for RaceInfo<T> {
+    type Err = ParseError;
+
+    fn from_str(sheet: &str) -> Result<Self, Self::Err> {
+        let mut lines = sheet.lines();
+        let mut field = |label: &str| {
+            let line = lines.next().and_then(|x| x.strip_prefix(label)).ok_or_else(|| ParseError::new(sheet, format!("missing {} line", label)))?;
+            let mut numbers = try_ints::<T>(line);
+            match (numbers.next(), numbers.next()) {
+                (Some(value), None) => value,
+                _ => Err(ParseError::new(line, "expected a single race")),
+            }
+        };
+        Ok(RaceInfo{time: field("Time:")?, distance: field("Distance:")?})
     }
 }
 
 fn parse_race_info(lines: &str) -> Vec<RaceInfo> {
+    let mut rows = ints_by_line::<u64>(lines);
+    let (line_time, line_distance) = (rows.next().unwrap(), rows.next().unwrap());
+    line_time.zip(line_distance).map(|(time, distance)| RaceInfo{time, distance}).collect::<Vec<RaceInfo>>()
+}
+
+/// Glues the digits of a line together as if the spaces between them weren't there,
+/// `None` if the result doesn't fit in `T`.
+fn concatenate<T: Int + From<u8>>(line: &str) -> Option<T> {
+    line.bytes().filter(u8::is_ascii_digit).try_fold(T::ZERO, |x, digit| x.checked_mul(T::from(10))?.checked_add(T::from(digit - b'0')))
+}
+
+fn parse_race_info_with_bad_kerning(lines: &str) -> Result<KernedRace, ParseError> {
     let mut lines_iter = lines.lines();
-    let line_time = lines_iter
-        .next()
-        .unwrap()
-        .split(':')
-        .nth(1)
-        .unwrap()
-        .split_whitespace();
-    let line_distance = lines_iter.next().unwrap().split(':').nth(1).unwrap().split_whitespace();
-    line_time.zip(line_distance).map(|(t, d)| RaceInfo{time: t.parse::<u64>().unwrap(), distance: d.parse::<u64>().unwrap()}).collect::<Vec<RaceInfo>>()
-}
-
-fn parse_race_info_with_bad_kerning(lines: &str) -> RaceInfo {
-    let mut lines_iter = lines.lines();
-    let time = lines_iter.next().unwrap().split(':').nth(1).unwrap().split_whitespace().map(|x| x.to_string()).reduce(|x, y| x + &y).unwrap().parse::<u64>().unwrap();
-    let distance = lines_iter.next().unwrap().split(':').nth(1).unwrap().split_whitespace().map(|x| x.to_string()).reduce(|x, y| x + &y).unwrap().parse::<u64>().unwrap();
-    RaceInfo{time, distance}
+    let mut field = |label: &str| {
+        let line = lines_iter.next().and_then(|x| x.strip_prefix(label)).ok_or_else(|| ParseError::new(lines, format!("missing {} line", label)))?;
+        match line.contains(|x: char| x.is_ascii_digit()) {
+            true => Ok(line),
+            false => Err(ParseError::new(line, format!("no digits after {}", label))),
+        }
+    };
+    let (time, distance) = (field("Time:")?, field("Distance:")?);
+    if let (Some(time), Some(distance)) = (concatenate(time), concatenate(distance)) {
+        return Ok(KernedRace::Narrow(RaceInfo{time, distance}));
+    }
+    match (concatenate(time), concatenate(distance)) {
+        (Some(time), Some(distance)) => Ok(KernedRace::Wide(RaceInfo{time, distance})),
+        _ => Err(ParseError::new(lines, "race doesn't fit in 128 bits")),
+    }
 }
 
 #[cfg(test)]
@@ -49,7 +118,21 @@ mod tests {
 
     #[test]
     fn test_parse_race_info_with_bad_kerning() {
-        assert_eq!(parse_race_info_with_bad_kerning("Time:      7  15   30\nDistance:  9  40  200"), RaceInfo{time: 71530, distance: 940200})
+        assert_eq!(parse_race_info_with_bad_kerning("Time:      7  15   30\nDistance:  9  40  200"), Ok(KernedRace::Narrow(RaceInfo{time: 71530, distance: 940200})));
+        let wide = parse_race_info_with_bad_kerning("Time:      1844674407  3709551616\nDistance:  1").unwrap();
+        assert_eq!(wide, KernedRace::Wide(RaceInfo{time: 1 << 64, distance: 1}));
+        assert_eq!(wide.number_ways_to_beat(), (1 << 64) - 1);
+        assert_eq!(parse_race_info_with_bad_kerning("Time:\nDistance:").unwrap_err().reason(), "no digits after Time:");
+        assert_eq!(parse_race_info_with_bad_kerning("Time: 7\nDist: 9").unwrap_err().reason(), "missing Distance: line");
+        assert_eq!(parse_race_info_with_bad_kerning("Time: 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0\nDistance: 1").unwrap_err().reason(), "race doesn't fit in 128 bits");
+    }
+
+    #[test]
+    fn test_race_info_from_str() {
+        assert_eq!("Time:      71530\nDistance:  940200".parse::<RaceInfo>(), Ok(RaceInfo{time: 71530, distance: 940200}));
+        assert_eq!("Time:      7  15   30\nDistance:  9  40  200".parse::<RaceInfo>().unwrap_err().reason(), "expected a single race");
+        assert_eq!("Time: 7".parse::<RaceInfo>().unwrap_err().reason(), "missing Distance: line");
+        assert_eq!("Time: 99999999999999999999\nDistance: 1".parse::<RaceInfo>().unwrap_err().reason(), "number out of range for u64");
     }
 
     #[test]
@@ -58,5 +141,7 @@ mod tests {
         assert_eq!(RaceInfo{time: 15, distance: 40}.number_ways_to_beat(), 8);
         assert_eq!(RaceInfo{time: 30, distance: 200}.number_ways_to_beat(), 9);
         assert_eq!(RaceInfo{time: 71530, distance: 940200}.number_ways_to_beat(), 71503);
+        assert_eq!(RaceInfo{time: 0, distance: 0}.number_ways_to_beat(), 0);
+        assert_eq!(RaceInfo{time: u64::MAX, distance: u64::MAX}.number_ways_to_beat(), u64::MAX - 3);
     }
 }
\ No newline at end of file
@@ -0,0 +1,58 @@
+use advent_of_code::sequence::{DifferenceTable, Direction};
+use std::fs::read_to_string;
+
+fn main() {
+    let file = read_to_string("./data/day9.txt").unwrap();
+    let report = parse_report(&file);
+    println!("{}", sum_extrapolated(&report, Direction::Forward));
+    println!("{}", sum_extrapolated(&report, Direction::Backward));
+}
+
+fn parse_history(line: &str) -> Vec<i64> {
+    line.split_whitespace()
+        .map(|x| x.parse::<i64>().unwrap())
+        .collect()
+}
+
+fn parse_report(lines: &str) -> Vec<DifferenceTable<i64>> {
+    lines
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| DifferenceTable::new(parse_history(line)))
+        .collect()
+}
+
+fn sum_extrapolated(report: &[DifferenceTable<i64>], direction: Direction) -> i64 {
+    report
+        .iter()
+        .map(|table| table.extrapolate(direction))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45";
+
+    #[test]
+    fn test_parse_history() {
+        assert_eq!(parse_history("0 -3  6 9"), vec![0, -3, 6, 9]);
+    }
+
+    #[test]
+    fn test_day9_part1() {
+        assert_eq!(
+            sum_extrapolated(&parse_report(EXAMPLE), Direction::Forward),
+            114
+        );
+    }
+
+    #[test]
+    fn test_day9_part2() {
+        assert_eq!(
+            sum_extrapolated(&parse_report(EXAMPLE), Direction::Backward),
+            2
+        );
+    }
+}
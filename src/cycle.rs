@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Index of the first state that repeats.
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Index of the earliest state equal to the one reached after `n` steps.
+    pub fn equivalent(&self, n: usize) -> usize {
+        match n < self.start {
+            true => n,
+            false => self.start + (n - self.start) % self.length,
+        }
+    }
+}
+
+/// Steps `state` in place until it repeats. On return `state` equals the state at `cycle.start`.
+pub fn find_cycle<T: Hash + Eq + Clone>(state: &mut T, step: impl FnMut(&mut T)) -> Cycle {
+    find_cycle_within(state, usize::MAX, step).expect("no cycle within usize::MAX steps")
+}
+
+/// Like [`find_cycle`], but gives up after `limit` steps, returning `None` with `state`
+/// stepped exactly `limit` times.
+pub fn find_cycle_within<T: Hash + Eq + Clone>(
+    state: &mut T,
+    limit: usize,
+    mut step: impl FnMut(&mut T),
+) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    for i in 0..=limit {
+        if let Some(&start) = seen.get(state) {
+            return Some(Cycle {
+                start,
+                length: i - start,
+            });
+        }
+        if i == limit {
+            break;
+        }
+        seen.insert(state.clone(), i);
+        step(state);
+    }
+    None
+}
+
+/// State after applying `step` `n` times, skipping whole cycles once a repeat is seen.
+pub fn nth_state<T: Hash + Eq + Clone>(mut state: T, n: usize, mut step: impl FnMut(&mut T)) -> T {
+    let Some(cycle) = find_cycle_within(&mut state, n, &mut step) else {
+        return state;
+    };
+    for _ in cycle.start..cycle.equivalent(n) {
+        step(&mut state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collatz(x: &mut u64) {
+        *x = match *x % 2 {
+            0 => *x / 2,
+            _ => 3 * *x + 1,
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let mut state = 6;
+        let cycle = find_cycle(&mut state, collatz);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 6,
+                length: 3
+            }
+        );
+        assert_eq!(state, 4);
+        assert_eq!(cycle.equivalent(3), 3);
+        assert_eq!(cycle.equivalent(10), 7);
+    }
+
+    #[test]
+    fn test_nth_state() {
+        assert_eq!(nth_state(6, 0, collatz), 6);
+        assert_eq!(nth_state(6, 4, collatz), 16);
+        assert_eq!(nth_state(6, 1_000_000_000, collatz), 2);
+        assert_eq!(nth_state(6, 1_000_000_001, collatz), 1);
+        assert_eq!(nth_state(0u64, 3, |x| *x = x.wrapping_add(1)), 3);
+    }
+
+    #[test]
+    fn test_find_cycle_within() {
+        let mut state = 6;
+        assert_eq!(find_cycle_within(&mut state, 5, collatz), None);
+        assert_eq!(state, 8);
+        let mut state = 6;
+        assert_eq!(
+            find_cycle_within(&mut state, 9, collatz),
+            Some(Cycle {
+                start: 6,
+                length: 3
+            })
+        );
+        assert_eq!(state, 4);
+    }
+}
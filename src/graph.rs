@@ -0,0 +1,162 @@
+use crate::intern::Interner;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Undirected graph with weighted edges between named nodes, parallel edges add up.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    names: Interner,
+    edges: Vec<HashMap<usize, u64>>,
+}
+
+/// A set of edges whose removal splits the graph in two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cut {
+    pub weight: u64,
+    /// Nodes on one side of the cut, the rest of the graph is on the other.
+    pub side: Vec<usize>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the node called `name`, adding it if it isn't in the graph yet.
+    pub fn node(&mut self, name: &str) -> usize {
+        let id = self.names.intern(name) as usize;
+        if id == self.edges.len() {
+            self.edges.push(HashMap::new());
+        }
+        id
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.names.get(name).map(|x| x as usize)
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        self.names.resolve(id as u32)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn add_edge(&mut self, a: usize, b: usize, weight: u64) {
+        assert_ne!(a, b, "self loops never cross a cut");
+        *self.edges[a].entry(b).or_default() += weight;
+        *self.edges[b].entry(a).or_default() += weight;
+    }
+
+    pub fn neighbours(&self, id: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.edges[id].iter().map(|(&x, &w)| (x, w))
+    }
+
+    /// Global minimum cut by Stoer-Wagner, `None` for graphs with fewer than two nodes.
+    ///
+    /// Every phase grows a set from an arbitrary node by repeatedly adding the most tightly
+    /// connected node; the last node added is then cut off by exactly its connection weight,
+    /// and is merged into the one added before it for the next phase.
+    pub fn min_cut(&self) -> Option<Cut> {
+        let mut edges = self.edges.clone();
+        let mut members = (0..self.len()).map(|x| vec![x]).collect::<Vec<_>>();
+        let mut active = (0..self.len()).collect::<Vec<usize>>();
+        let mut best: Option<Cut> = None;
+        while active.len() > 1 {
+            let mut connection = vec![0u64; self.len()];
+            let mut added = vec![false; self.len()];
+            let mut queue = active.iter().map(|&x| (0, x)).collect::<BinaryHeap<_>>();
+            let mut order = Vec::with_capacity(active.len());
+            while let Some((weight, v)) = queue.pop() {
+                if added[v] || weight != connection[v] {
+                    continue;
+                }
+                added[v] = true;
+                order.push(v);
+                for (&u, &w) in &edges[v] {
+                    if !added[u] {
+                        connection[u] += w;
+                        queue.push((connection[u], u));
+                    }
+                }
+            }
+            let (s, t) = (order[order.len() - 2], order[order.len() - 1]);
+            if best.as_ref().is_none_or(|x| connection[t] < x.weight) {
+                best = Some(Cut {
+                    weight: connection[t],
+                    side: members[t].clone(),
+                });
+            }
+            for (u, w) in std::mem::take(&mut edges[t]) {
+                edges[u].remove(&t);
+                if u != s {
+                    *edges[s].entry(u).or_default() += w;
+                    *edges[u].entry(s).or_default() += w;
+                }
+            }
+            let merged = std::mem::take(&mut members[t]);
+            members[s].extend(merged);
+            active.retain(|&x| x != t);
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str, u64)]) -> Graph {
+        let mut graph = Graph::new();
+        for &(a, b, weight) in edges {
+            let (a, b) = (graph.node(a), graph.node(b));
+            graph.add_edge(a, b, weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_interning() {
+        let mut graph = graph(&[("a", "b", 1), ("b", "c", 2), ("a", "b", 3)]);
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.id("c"), Some(2));
+        assert_eq!(graph.name(1), "b");
+        assert_eq!(graph.node("a"), 0);
+        assert_eq!(graph.neighbours(0).collect::<Vec<_>>(), vec![(1, 4)]);
+    }
+
+    #[test]
+    fn test_min_cut() {
+        // the example graph from the Stoer-Wagner paper
+        let graph = graph(&[
+            ("1", "2", 2),
+            ("1", "5", 3),
+            ("2", "3", 3),
+            ("2", "5", 2),
+            ("2", "6", 2),
+            ("3", "4", 4),
+            ("3", "7", 2),
+            ("4", "7", 2),
+            ("4", "8", 2),
+            ("5", "6", 3),
+            ("6", "7", 1),
+            ("7", "8", 3),
+        ]);
+        let cut = graph.min_cut().unwrap();
+        assert_eq!(cut.weight, 4);
+        let mut side = cut.side.iter().map(|&x| graph.name(x)).collect::<Vec<_>>();
+        side.sort();
+        assert!(side == ["3", "4", "7", "8"] || side == ["1", "2", "5", "6"]);
+        assert_eq!(Graph::new().min_cut(), None);
+    }
+
+    #[test]
+    fn test_disconnected_min_cut() {
+        let graph = graph(&[("a", "b", 5), ("c", "d", 5)]);
+        assert_eq!(graph.min_cut().unwrap().weight, 0);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::math::modulo;
+use std::ops::{Index, IndexMut};
+
+/// `(x, y)` coordinates, `x` being the column and `y` the row, as in day 3.
+pub type Position = (i32, i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn offset(self) -> Position {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        self.turn_left().opposite()
+    }
+
+    pub fn step(self, position: Position) -> Position {
+        let (dx, dy) = self.offset();
+        (position.0 + dx, position.1 + dy)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height);
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Panics if the non-empty lines of `text` don't all have the same length.
+    pub fn parse_with(text: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in text.lines().map(str::trim_end).filter(|x| !x.is_empty()) {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let length = cells.len() - before;
+            let expected = *width.get_or_insert(length);
+            assert_eq!(
+                length, expected,
+                "row {} is {} wide instead of {}: {:?}",
+                height, length, expected, line
+            );
+            height += 1;
+        }
+        Self::new(width.unwrap_or(0), height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, position: Position) -> bool {
+        0 <= position.0
+            && (position.0 as usize) < self.width
+            && 0 <= position.1
+            && (position.1 as usize) < self.height
+    }
+
+    fn index_of(&self, position: Position) -> Option<usize> {
+        self.in_bounds(position)
+            .then(|| position.1 as usize * self.width + position.0 as usize)
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let (width, height) = (self.width as i32, self.height as i32);
+        (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.iter().find(|(_, x)| predicate(x)).map(|(p, _)| p)
+    }
+
+    pub fn neighbours(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = (Direction, Position)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .map(move |d| (d, d.step(position)))
+            .filter(|(_, p)| self.in_bounds(*p))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// View of the grid repeated infinitely in every direction.
+    pub fn wrapping(&self) -> Wrapping<'_, T> {
+        Wrapping { grid: self }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Wrapping<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<'a, T> Wrapping<'a, T> {
+    /// The position inside the underlying grid that `position` maps to.
+    pub fn wrap(&self, position: Position) -> Position {
+        (
+            modulo(position.0, self.grid.width as i32),
+            modulo(position.1, self.grid.height as i32),
+        )
+    }
+
+    pub fn get(&self, position: Position) -> &'a T {
+        &self.grid[self.wrap(position)]
+    }
+
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = (Direction, Position)> {
+        Direction::ALL
+            .into_iter()
+            .map(move |d| (d, d.step(position)))
+    }
+}
+
+impl<T> Index<Position> for Wrapping<'_, T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {:?} is outside of the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {:?} is outside of the grid", position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_with() {
+        let grid = Grid::parse_with("#.\n.#\n..\n", |c| c == '#');
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert!(grid[(1, 1)]);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, -1)), None);
+        assert_eq!(grid.find(|&x| x), Some((0, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 is 1 wide instead of 3")]
+    fn test_parse_ragged() {
+        Grid::parse_with("###\n#\n##", |c| c);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::parse_with("...\n...", |c| c);
+        assert_eq!(
+            grid.neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![(Direction::East, (1, 0)), (Direction::South, (0, 1))]
+        );
+        assert_eq!(grid.neighbours((1, 1)).count(), 3);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let grid = Grid::parse_with("ab\ncd", |c| c);
+        let wrapping = grid.wrapping();
+        assert_eq!(wrapping[(0, 0)], 'a');
+        assert_eq!(wrapping[(3, 0)], 'b');
+        assert_eq!(wrapping[(-1, -1)], 'd');
+        assert_eq!(wrapping.wrap((-4, 5)), (0, 1));
+        assert_eq!(wrapping.neighbours((0, 0)).count(), 4);
+    }
+
+    #[test]
+    fn test_direction_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.step((3, 3)), (2, 3));
+    }
+}
@@ -0,0 +1,321 @@
+use crate::math::Int;
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+use std::str::{self, FromStr};
+
+/// Why a piece of puzzle input couldn't be turned into a record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    input: String,
+    reason: String,
+}
+
+impl ParseError {
+    pub fn new(input: &str, reason: impl Display) -> Self {
+        Self {
+            input: input.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} in {:?}", self.reason, self.input)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A block of consecutive non-blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// Name before the `:` on the first line, only set for [`named_sections`].
+    pub header: Option<&'a str>,
+    pub body: &'a str,
+    /// Zero-based index of the line `body` starts on within the whole input.
+    pub line_offset: usize,
+}
+
+impl<'a> Section<'a> {
+    /// Lines of the body paired with their zero-based index within the whole input.
+    pub fn lines(&self) -> impl Iterator<Item = (usize, &'a str)> {
+        let offset = self.line_offset;
+        self.body
+            .lines()
+            .enumerate()
+            .map(move |(i, x)| (offset + i, x))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Sections<'a> {
+    text: &'a str,
+    position: usize,
+    line: usize,
+    named: bool,
+}
+
+impl<'a> Sections<'a> {
+    fn next_line(&self) -> Option<&'a str> {
+        self.text[self.position..].split_inclusive('\n').next()
+    }
+
+    fn advance(&mut self, line: &str) {
+        self.position += line.len();
+        self.line += 1;
+    }
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Header names are words made of letters, digits and dashes, like `seed-to-soil map`.
+fn is_header_name(text: &str) -> bool {
+    !text.trim().is_empty()
+        && text
+            .chars()
+            .all(|x| x.is_ascii_alphanumeric() || x == '-' || x == ' ')
+}
+
+fn split_header(section: Section<'_>) -> Section<'_> {
+    let first_line = section.body.lines().next().unwrap_or_default();
+    let Some(colon) = first_line
+        .find(':')
+        .filter(|&x| is_header_name(&first_line[..x]))
+    else {
+        return section;
+    };
+    let header = Some(first_line[..colon].trim());
+    let rest = &section.body[colon + 1..];
+    if is_blank(&first_line[colon + 1..]) {
+        let body = rest.split_once('\n').map_or("", |(_, x)| x);
+        Section {
+            header,
+            body,
+            line_offset: section.line_offset + 1,
+        }
+    } else {
+        Section {
+            header,
+            body: rest.trim_start_matches([' ', '\t']),
+            line_offset: section.line_offset,
+        }
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Section<'a>> {
+        while let Some(line) = self.next_line().filter(|x| is_blank(x)) {
+            self.advance(line);
+        }
+        let (start, line_offset) = (self.position, self.line);
+        let mut end = start;
+        while let Some(line) = self.next_line().filter(|x| !is_blank(x)) {
+            end = self.position + line.trim_end_matches(['\r', '\n']).len();
+            self.advance(line);
+        }
+        if start == end {
+            return None;
+        }
+        let section = Section {
+            header: None,
+            body: &self.text[start..end],
+            line_offset,
+        };
+        match self.named {
+            true => Some(split_header(section)),
+            false => Some(section),
+        }
+    }
+}
+
+/// Splits puzzle input into sections separated by one or more blank lines.
+pub fn sections(text: &str) -> Sections<'_> {
+    Sections {
+        text,
+        position: 0,
+        line: 0,
+        named: false,
+    }
+}
+
+/// Like [`sections`], but treats a leading `name:` as the section header, e.g. `seeds: 79 14`
+/// or a `seed-to-soil map:` line on its own.
+pub fn named_sections(text: &str) -> Sections<'_> {
+    Sections {
+        named: true,
+        ..sections(text)
+    }
+}
+
+/// Splits puzzle input into blocks separated by one or more blank lines, i.e. the bodies of
+/// [`sections`].
+pub fn paragraphs(text: &str) -> impl Iterator<Item = &str> {
+    sections(text).map(|x| x.body)
+}
+
+/// Iterator over the integers embedded in a piece of input, see [`try_ints`].
+#[derive(Debug, Clone)]
+pub struct TryInts<'a, T> {
+    bytes: &'a [u8],
+    position: usize,
+    target: PhantomData<T>,
+}
+
+impl<T: Int + FromStr> Iterator for TryInts<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.bytes;
+        let start = self.position + bytes[self.position..].iter().position(u8::is_ascii_digit)?;
+        let end = bytes[start..]
+            .iter()
+            .position(|x| !x.is_ascii_digit())
+            .map_or(bytes.len(), |x| start + x);
+        self.position = end;
+        let start = match T::SIGNED && start > 0 && bytes[start - 1] == b'-' {
+            true => start - 1,
+            false => start,
+        };
+        let digits = str::from_utf8(&bytes[start..end]).unwrap();
+        Some(digits.parse().map_err(|_| {
+            ParseError::new(digits, format!("number out of range for {}", type_name::<T>()))
+        }))
+    }
+}
+
+/// Every run of ASCII digits in `input` parsed as `T`, skipping whatever lies in between.
+/// A `-` right before the digits is kept as a sign for signed `T` only, so `3-5` gives
+/// `3, 5` as unsigned and `3, -5` as signed. Numbers that don't fit in `T` are errors.
+pub fn try_ints<T>(input: &(impl AsRef<[u8]> + ?Sized)) -> TryInts<'_, T> {
+    TryInts {
+        bytes: input.as_ref(),
+        position: 0,
+        target: PhantomData,
+    }
+}
+
+/// Like [`TryInts`], but panics on numbers that don't fit, see [`ints`].
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T>(TryInts<'a, T>);
+
+impl<T: Int + FromStr> Iterator for Ints<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.next().map(|x| x.unwrap_or_else(|e| panic!("{}", e)))
+    }
+}
+
+/// [`try_ints`] for input known to be well-formed, panics on numbers that don't fit in `T`.
+pub fn ints<T>(input: &(impl AsRef<[u8]> + ?Sized)) -> Ints<'_, T> {
+    Ints(try_ints(input))
+}
+
+/// Like [`ints`], with a separate iterator for every line of `input`.
+pub fn ints_by_line<T>(input: &(impl AsRef<[u8]> + ?Sized)) -> impl Iterator<Item = Ints<'_, T>> {
+    input.as_ref().split(|&x| x == b'\n').map(ints::<T>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paragraphs() {
+        assert_eq!(
+            paragraphs("a\nb\n\nc\n\n\n\nd\n").collect::<Vec<&str>>(),
+            vec!["a\nb", "c", "d"]
+        );
+        assert_eq!(
+            paragraphs("a\r\n\r\nb\r\n").collect::<Vec<&str>>(),
+            vec!["a", "b"]
+        );
+        assert_eq!(paragraphs("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn test_sections_line_offsets() {
+        let parsed = sections("\nfirst\nsecond\n  \nthird").collect::<Vec<Section>>();
+        assert_eq!(
+            parsed,
+            vec![
+                Section {
+                    header: None,
+                    body: "first\nsecond",
+                    line_offset: 1
+                },
+                Section {
+                    header: None,
+                    body: "third",
+                    line_offset: 4
+                }
+            ]
+        );
+        assert_eq!(
+            parsed[0].lines().collect::<Vec<(usize, &str)>>(),
+            vec![(1, "first"), (2, "second")]
+        );
+    }
+
+    #[test]
+    fn test_named_sections() {
+        let text = "seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nno header";
+        let parsed = named_sections(text).collect::<Vec<Section>>();
+        assert_eq!(parsed[0].header, Some("seeds"));
+        assert_eq!(parsed[0].body, "79 14 55 13");
+        assert_eq!(parsed[0].line_offset, 0);
+        assert_eq!(parsed[1].header, Some("seed-to-soil map"));
+        assert_eq!(parsed[1].body, "50 98 2\n52 50 48");
+        assert_eq!(parsed[1].line_offset, 3);
+        assert_eq!(parsed[2].header, None);
+        assert_eq!(parsed[2].body, "no header");
+        let rules = "px{a<2006:qkq,m>2090:A,rfg}\npv{a>1716:R,A}";
+        let parsed = named_sections(rules).collect::<Vec<Section>>();
+        assert_eq!(parsed[0].header, None);
+        assert_eq!(parsed[0].body, rules);
+    }
+
+    #[test]
+    fn test_ints() {
+        let line = "Game 12: x=-3, y=4..-15";
+        assert_eq!(ints::<u32>(line).collect::<Vec<_>>(), vec![12, 3, 4, 15]);
+        assert_eq!(ints::<i64>(line).collect::<Vec<_>>(), vec![12, -3, 4, -15]);
+        assert_eq!(
+            ints::<u8>(b"7 -- 255".as_slice()).collect::<Vec<_>>(),
+            vec![7, 255]
+        );
+        assert_eq!(ints::<i32>("no numbers").next(), None);
+        let rows = ints_by_line::<u64>("Time: 7 15\nDistance: 9 40\n")
+            .map(|x| x.collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec![vec![7, 15], vec![9, 40], vec![]]);
+        let checked = try_ints::<u8>("1 -256").collect::<Vec<_>>();
+        assert_eq!(checked[0], Ok(1));
+        assert_eq!(
+            checked[1].as_ref().unwrap_err().reason(),
+            "number out of range for u8"
+        );
+        assert_eq!(try_ints::<i8>("-128").next(), Some(Ok(-128)));
+        assert_eq!(
+            try_ints::<i8>("-129").next().unwrap().unwrap_err().to_string(),
+            "number out of range for i8 in \"-129\""
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "number out of range for u8 in \"256\"")]
+    fn test_ints_overflow() {
+        ints::<u8>("1 256").for_each(drop);
+    }
+}
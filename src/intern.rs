@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+/// Maps names to dense `u32` ids in order of first appearance, so per-name data can live in
+/// a `Vec` indexed by id instead of a map keyed by strings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Interner {
+    names: Vec<String>,
+    ids: HashMap<String, u32>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of `name`, assigning the next free one if it wasn't seen before.
+    pub fn intern(&mut self, name: &str) -> u32 {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = u32::try_from(self.names.len()).expect("too many interned names");
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        id
+    }
+
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    /// Name behind `id`, panics if `id` wasn't handed out by this interner.
+    pub fn resolve(&self, id: u32) -> &str {
+        &self.names[id as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Names paired with their ids, in id order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(i, x)| (i as u32, x.as_str()))
+    }
+}
+
+impl<'a> FromIterator<&'a str> for Interner {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(names: I) -> Self {
+        let mut interner = Self::new();
+        for name in names {
+            interner.intern(name);
+        }
+        interner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let mut interner = Interner::new();
+        assert_eq!(interner.intern("broadcaster"), 0);
+        assert_eq!(interner.intern("a"), 1);
+        assert_eq!(interner.intern("broadcaster"), 0);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.get("a"), Some(1));
+        assert_eq!(interner.get("b"), None);
+        assert_eq!(interner.resolve(1), "a");
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let interner = ["red", "green", "red", "blue"]
+            .into_iter()
+            .collect::<Interner>();
+        assert_eq!(
+            interner.iter().collect::<Vec<_>>(),
+            vec![(0, "red"), (1, "green"), (2, "blue")]
+        );
+    }
+}
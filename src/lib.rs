@@ -0,0 +1,12 @@
+pub mod cycle;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod intern;
+pub mod linalg;
+pub mod math;
+pub mod polygon;
+pub mod scan;
+pub mod search;
+pub mod sequence;
+pub mod space;
@@ -0,0 +1,249 @@
+use crate::math::gcd;
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Exact fraction over `i128`, always kept reduced with a positive denominator.
+/// Arithmetic panics instead of wrapping if a reduced result doesn't fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational {
+        numerator: 0,
+        denominator: 1,
+    };
+    pub const ONE: Rational = Rational {
+        numerator: 1,
+        denominator: 1,
+    };
+
+    pub fn new(numerator: i128, denominator: i128) -> Self {
+        assert_ne!(denominator, 0, "zero denominator");
+        let divisor = gcd(numerator, denominator) * denominator.signum();
+        Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        }
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.numerator
+    }
+
+    pub fn denominator(&self) -> i128 {
+        self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.denominator == 1).then_some(self.numerator)
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let g = gcd(self.denominator, other.denominator);
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator / g)?
+            .checked_add(other.numerator.checked_mul(self.denominator / g)?)?;
+        let denominator = (self.denominator / g).checked_mul(other.denominator)?;
+        Some(Self::new(numerator, denominator))
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let g1 = gcd(self.numerator, other.denominator);
+        let g2 = gcd(other.numerator, self.denominator);
+        let numerator = (self.numerator / g1).checked_mul(other.numerator / g2)?;
+        let denominator = (self.denominator / g2).checked_mul(other.denominator / g1)?;
+        Some(Self::new(numerator, denominator))
+    }
+
+    pub fn recip(self) -> Self {
+        Self::new(self.denominator, self.numerator)
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Rational {
+            fn from(value: $t) -> Self {
+                Self {
+                    numerator: value as i128,
+                    denominator: 1,
+                }
+            }
+        }
+    )*};
+}
+
+impl_from_integer!(i32, i64, i128);
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.checked_add(other).expect("rational overflow")
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other).expect("rational overflow")
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        self.checked_mul(other.recip()).expect("rational overflow")
+    }
+}
+
+impl Ord for Rational {
+    /// Compares integer parts first and then the reciprocals of the fractional parts, so it
+    /// never has to cross-multiply.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (mut a, mut b) = (self.numerator, self.denominator);
+        let (mut c, mut d) = (other.numerator, other.denominator);
+        loop {
+            let (qa, qc) = (a.div_euclid(b), c.div_euclid(d));
+            let (ra, rc) = (a.rem_euclid(b), c.rem_euclid(d));
+            match (qa.cmp(&qc), ra == 0, rc == 0) {
+                (Ordering::Equal, false, false) => {
+                    // ra / b < rc / d exactly when d / rc < b / ra
+                    (a, b, c, d) = (d, rc, b, ra);
+                }
+                (Ordering::Equal, true, true) => return Ordering::Equal,
+                (Ordering::Equal, true, false) => return Ordering::Less,
+                (Ordering::Equal, false, true) => return Ordering::Greater,
+                (ordering, _, _) => return ordering,
+            }
+        }
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.denominator {
+            1 => write!(f, "{}", self.numerator),
+            _ => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+/// Solves `matrix * x = rhs` by Gauss-Jordan elimination, `None` if the matrix is singular.
+pub fn solve(mut matrix: Vec<Vec<Rational>>, mut rhs: Vec<Rational>) -> Option<Vec<Rational>> {
+    let n = rhs.len();
+    assert!(matrix.len() == n && matrix.iter().all(|row| row.len() == n));
+    for column in 0..n {
+        let pivot = (column..n).find(|&row| !matrix[row][column].is_zero())?;
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+        let scale = matrix[column][column].recip();
+        for value in matrix[column].iter_mut().skip(column) {
+            *value = *value * scale;
+        }
+        rhs[column] = rhs[column] * scale;
+        let pivot_row = matrix[column].clone();
+        for row in 0..n {
+            let factor = matrix[row][column];
+            if row == column || factor.is_zero() {
+                continue;
+            }
+            for (value, pivot) in matrix[row].iter_mut().zip(&pivot_row).skip(column) {
+                *value = *value - factor * *pivot;
+            }
+            rhs[row] = rhs[row] - factor * rhs[column];
+        }
+    }
+    Some(rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(numerator: i128, denominator: i128) -> Rational {
+        Rational::new(numerator, denominator)
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(r(6, -4), r(-3, 2));
+        assert_eq!(r(-3, 2).numerator(), -3);
+        assert_eq!(r(-3, 2).denominator(), 2);
+        assert_eq!(r(0, -5), Rational::ZERO);
+        assert_eq!(r(8, 4).to_integer(), Some(2));
+        assert_eq!(r(1, 4).to_integer(), None);
+        assert_eq!(r(-3, 2).to_string(), "-3/2");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 2) - r(3, 4), r(-1, 4));
+        assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+        assert_eq!(r(2, 3) / r(-4, 9), r(-3, 2));
+        let big = Rational::from(i128::MAX / 3);
+        assert_eq!(big * r(3, i128::MAX / 3), Rational::from(3));
+        assert_eq!(big.checked_mul(Rational::from(4)), None);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(r(7, 3) > Rational::from(2));
+        assert!(r(22, 7) > r(355, 113));
+        let huge = i128::MAX / 2;
+        assert!(r(huge, huge - 1) < r(huge - 1, huge - 2));
+        assert_eq!(r(huge, 3).cmp(&r(huge, 3)), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_solve() {
+        let matrix = vec![
+            vec![r(2, 1), r(1, 1), r(-1, 1)],
+            vec![r(-3, 1), r(-1, 1), r(2, 1)],
+            vec![r(-2, 1), r(1, 1), r(2, 1)],
+        ];
+        let rhs = vec![r(8, 1), r(-11, 1), r(-3, 1)];
+        assert_eq!(solve(matrix, rhs), Some(vec![r(2, 1), r(3, 1), r(-1, 1)]));
+        let singular = vec![vec![r(1, 1), r(2, 1)], vec![r(2, 1), r(4, 1)]];
+        assert_eq!(solve(singular, vec![r(1, 1), r(2, 1)]), None);
+    }
+}
@@ -0,0 +1,341 @@
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+pub trait Int:
+    Copy
+    + Ord
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+    const SIGNED: bool;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn abs_value(self) -> Self;
+}
+
+pub trait SignedInt: Int + Neg<Output = Self> {}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const TWO: Self = 2;
+            const SIGNED: bool = false;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn abs_value(self) -> Self {
+                self
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const TWO: Self = 2;
+            const SIGNED: bool = true;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn abs_value(self) -> Self {
+                self.abs()
+            }
+        }
+
+        impl SignedInt for $t {}
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "arithmetic overflow")
+    }
+}
+
+impl Error for Overflow {}
+
+pub fn gcd<T: Int>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs_value(), b.abs_value());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+pub fn lcm<T: Int>(a: T, b: T) -> T {
+    checked_lcm(a, b).unwrap()
+}
+
+pub fn checked_lcm<T: Int>(a: T, b: T) -> Result<T, Overflow> {
+    if a == T::ZERO || b == T::ZERO {
+        return Ok(T::ZERO);
+    }
+    checked_mul((a / gcd(a, b)).abs_value(), b.abs_value())
+}
+
+pub fn gcd_all<T: Int>(values: &[T]) -> T {
+    values.iter().fold(T::ZERO, |acc, &x| gcd(acc, x))
+}
+
+pub fn lcm_all<T: Int>(values: &[T]) -> T {
+    values.iter().fold(T::ONE, |acc, &x| lcm(acc, x))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g` and `g` is non-negative.
+pub fn extended_gcd<T: SignedInt>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (T::ONE, T::ZERO);
+    let (mut old_t, mut t) = (T::ZERO, T::ONE);
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+        (old_t, t) = (t, old_t - q * t);
+    }
+    if old_r < T::ZERO {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Remainder of `a` divided by `m`, always in `0..m` for positive `m`.
+pub fn modulo<T: Int>(a: T, m: T) -> T {
+    let r = a % m;
+    if r < T::ZERO {
+        r + m
+    } else {
+        r
+    }
+}
+
+fn add_mod<T: Int>(a: T, b: T, m: T) -> T {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// Computes `a * b mod m` without overflowing, even when the product doesn't fit in `T`.
+pub fn mul_mod<T: Int>(a: T, b: T, m: T) -> T {
+    let (mut a, mut b) = (modulo(a, m), modulo(b, m));
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+    let mut result = T::ZERO;
+    while b > T::ZERO {
+        if b % T::TWO == T::ONE {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b / T::TWO;
+    }
+    result
+}
+
+pub fn mod_inverse<T: SignedInt>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(modulo(a, m), m);
+    if g != T::ONE {
+        return None;
+    }
+    Some(modulo(x, m))
+}
+
+/// Solves a system of `x ≡ residue (mod modulus)` congruences, moduli need not be coprime.
+/// Returns the smallest non-negative solution together with the combined modulus,
+/// or `None` if the system is inconsistent or the combined modulus doesn't fit in `T`.
+pub fn crt<T: SignedInt>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let (mut x, mut m) = (T::ZERO, T::ONE);
+    for &(residue, modulus) in congruences {
+        let residue = modulo(residue, modulus);
+        let (g, p, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != T::ZERO {
+            return None;
+        }
+        let step = modulus / g;
+        let t = mul_mod(diff / g, p, step);
+        let combined = m.checked_mul(step)?;
+        x = x + m * t;
+        m = combined;
+    }
+    Some((x, m))
+}
+
+pub fn checked_mul<T: Int>(a: T, b: T) -> Result<T, Overflow> {
+    a.checked_mul(b).ok_or(Overflow)
+}
+
+pub fn checked_product<T: Int>(values: impl IntoIterator<Item = T>) -> Result<T, Overflow> {
+    values.into_iter().try_fold(T::ONE, checked_mul)
+}
+
+/// Floor of the square root of a non-negative `n`.
+pub fn isqrt<T: Int>(n: T) -> T {
+    assert!(n >= T::ZERO, "square root of negative number {}", n);
+    if n < T::TWO {
+        return n;
+    }
+    let mut x = n;
+    let mut y = n / T::TWO + n % T::TWO;
+    while y < x {
+        x = y;
+        y = (x + n / x) / T::TWO;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u8, 7), 7);
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+        assert_eq!(gcd_all(&[48u16, 36, 120]), 12);
+        assert_eq!(lcm_all(&[2u128, 3, 4, 5]), 60);
+        assert_eq!(lcm_all::<u64>(&[]), 1);
+        assert_eq!(checked_lcm(200u8, 3), Err(Overflow));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240i32, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-6i8, 4).0, 2);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3i32, 11), Some(4));
+        assert_eq!(mod_inverse(-3i64, 11), Some(7));
+        assert_eq!(mod_inverse(6i16, 9), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1i32, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1i32, 4), (2, 6)]), None);
+        assert_eq!(crt::<i8>(&[(1, 100), (2, 99)]), None);
+    }
+
+    #[test]
+    fn test_mul_mod_wide() {
+        assert_eq!(mul_mod(u64::MAX - 1, u64::MAX - 1, u64::MAX), 1);
+        assert_eq!(mul_mod(200u8, 200, 251), (200 * 200 % 251) as u8);
+    }
+
+    #[test]
+    fn test_checked_mul() {
+        assert_eq!(checked_mul(16u8, 15), Ok(240));
+        assert_eq!(checked_mul(16u8, 16), Err(Overflow));
+        assert_eq!(checked_product([2i32, 3, 7]), Ok(42));
+        assert_eq!(checked_product([u64::MAX, 2]), Err(Overflow));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0u32), 0);
+        assert_eq!(isqrt(15u8), 3);
+        assert_eq!(isqrt(16i64), 4);
+        assert_eq!(isqrt(u8::MAX), 15);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_gcd_lcm(a in 1u64..1_000_000, b in 1u64..1_000_000) {
+            let g = gcd(a, b);
+            prop_assert_eq!(a % g, 0);
+            prop_assert_eq!(b % g, 0);
+            prop_assert_eq!(g * lcm(a, b), a * b);
+        }
+
+        #[test]
+        fn prop_extended_gcd(a in any::<i32>(), b in any::<i32>()) {
+            let (g, x, y) = extended_gcd(a as i64, b as i64);
+            prop_assert_eq!(g, gcd(a as i64, b as i64));
+            prop_assert_eq!(a as i64 * x + b as i64 * y, g);
+        }
+
+        #[test]
+        fn prop_mod_inverse(a in any::<i64>(), m in 2i64..1_000_000_007) {
+            match mod_inverse(a, m) {
+                Some(inv) => prop_assert_eq!(mul_mod(a, inv, m), 1),
+                None => prop_assert_ne!(gcd(a, m), 1),
+            }
+        }
+
+        #[test]
+        fn prop_crt(congruences in prop::collection::vec((any::<i64>(), 1i64..1000), 1..5)) {
+            if let Some((x, m)) = crt(&congruences) {
+                prop_assert!(0 <= x && x < m);
+                for (r, n) in congruences {
+                    prop_assert_eq!(modulo(x, n), modulo(r, n));
+                }
+            }
+        }
+
+        #[test]
+        fn prop_crt_coprime(x in 0i64..(7 * 11 * 13)) {
+            prop_assert_eq!(crt(&[(x % 7, 7), (x % 11, 11), (x % 13, 13)]), Some((x, 1001)));
+        }
+
+        #[test]
+        fn prop_mul_mod(a in any::<u64>(), b in any::<u64>(), m in 1u64..) {
+            prop_assert_eq!(mul_mod(a, b, m) as u128, a as u128 * b as u128 % m as u128);
+        }
+
+        #[test]
+        fn prop_checked_mul(a in any::<u32>(), b in any::<u32>()) {
+            let wide = a as u64 * b as u64;
+            prop_assert_eq!(checked_mul(a, b).ok(), u32::try_from(wide).ok());
+        }
+
+        #[test]
+        fn prop_isqrt(n in any::<u64>()) {
+            let r = isqrt(n) as u128;
+            prop_assert!(r * r <= n as u128);
+            prop_assert!((r + 1) * (r + 1) > n as u128);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::grid::Direction;
+use crate::math::gcd;
+
+pub type Vertex = (i64, i64);
+
+/// Vertices visited when walking `moves` from the origin. The last move is expected to
+/// return to the origin, which is not repeated at the end.
+pub fn trace(moves: impl IntoIterator<Item = (Direction, i64)>) -> Vec<Vertex> {
+    let mut current = (0, 0);
+    let mut vertices = vec![current];
+    for (direction, distance) in moves {
+        let (dx, dy) = direction.offset();
+        current = (
+            current.0 + dx as i64 * distance,
+            current.1 + dy as i64 * distance,
+        );
+        vertices.push(current);
+    }
+    if vertices.len() > 1 && vertices.last() == vertices.first() {
+        vertices.pop();
+    }
+    vertices
+}
+
+fn edges(vertices: &[Vertex]) -> impl Iterator<Item = (Vertex, Vertex)> + '_ {
+    vertices
+        .iter()
+        .copied()
+        .zip(vertices.iter().copied().cycle().skip(1))
+}
+
+/// Twice the enclosed area, by the shoelace formula. Always an integer for lattice polygons.
+pub fn twice_area(vertices: &[Vertex]) -> i128 {
+    edges(vertices)
+        .map(|(a, b)| a.0 as i128 * b.1 as i128 - b.0 as i128 * a.1 as i128)
+        .sum::<i128>()
+        .abs()
+}
+
+/// Lattice points lying on the edges.
+pub fn boundary_points(vertices: &[Vertex]) -> i128 {
+    edges(vertices)
+        .map(|(a, b)| gcd(b.0 as i128 - a.0 as i128, b.1 as i128 - a.1 as i128))
+        .sum()
+}
+
+/// Lattice points strictly inside, by Pick's theorem `A = i + b / 2 - 1`.
+pub fn interior_points(vertices: &[Vertex]) -> i128 {
+    (twice_area(vertices) - boundary_points(vertices)) / 2 + 1
+}
+
+/// Lattice points inside or on the boundary, e.g. the tiles a dug trench encloses.
+pub fn lattice_points(vertices: &[Vertex]) -> i128 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace() {
+        assert_eq!(
+            trace([
+                (Direction::East, 2),
+                (Direction::South, 3),
+                (Direction::West, 2),
+                (Direction::North, 3)
+            ]),
+            vec![(0, 0), (2, 0), (2, 3), (0, 3)]
+        );
+    }
+
+    #[test]
+    fn test_rectangle() {
+        let rectangle = [(0, 0), (2, 0), (2, 3), (0, 3)];
+        assert_eq!(twice_area(&rectangle), 12);
+        assert_eq!(boundary_points(&rectangle), 10);
+        assert_eq!(interior_points(&rectangle), 2);
+        assert_eq!(lattice_points(&rectangle), 12);
+    }
+
+    #[test]
+    fn test_triangle() {
+        let triangle = [(0, 0), (4, 0), (0, 4)];
+        assert_eq!(twice_area(&triangle), 16);
+        assert_eq!(boundary_points(&triangle), 12);
+        assert_eq!(interior_points(&triangle), 3);
+    }
+
+    #[test]
+    fn test_huge_coordinates() {
+        let side = 3_000_000_000_000;
+        let square = [(0, 0), (side, 0), (side, side), (0, side)];
+        assert_eq!(twice_area(&square), 2 * side as i128 * side as i128);
+        assert_eq!(lattice_points(&square), (side as i128 + 1).pow(2));
+    }
+}
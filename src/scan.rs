@@ -0,0 +1,315 @@
+use crate::input::ParseError;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Byte range of the name of the first `{name}` placeholder at or after `from`, skipping
+/// `{{` and `}}` escapes. A `const fn` so [`scan!`](crate::scan!) can check its pattern while
+/// compiling.
+const fn next_placeholder(
+    pattern: &[u8],
+    from: usize,
+) -> Result<Option<(usize, usize)>, &'static str> {
+    let mut i = from;
+    while i < pattern.len() {
+        let escaped = i + 1 < pattern.len() && pattern[i + 1] == pattern[i];
+        match pattern[i] {
+            b'{' | b'}' if escaped => i += 2,
+            b'}' => return Err("unmatched } in pattern, write }} for a literal one"),
+            b'{' => {
+                let mut end = i + 1;
+                while end < pattern.len() && pattern[end] != b'}' {
+                    if pattern[end] == b'{' {
+                        return Err("unclosed placeholder in pattern");
+                    }
+                    end += 1;
+                }
+                return match end {
+                    _ if end == pattern.len() => Err("unclosed placeholder in pattern"),
+                    _ if end == i + 1 => Err("empty placeholder in pattern"),
+                    _ => Ok(Some((i + 1, end))),
+                };
+            }
+            _ => i += 1,
+        }
+    }
+    Ok(None)
+}
+
+const fn name_at(pattern: &[u8], (start, end): (usize, usize)) -> &[u8] {
+    pattern.split_at(end).0.split_at(start).1
+}
+
+const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// What's wrong with `pattern` on its own, if anything.
+const fn pattern_shape_error(pattern: &str) -> Option<&'static str> {
+    let pattern = pattern.as_bytes();
+    let mut from = 0;
+    let mut after_placeholder = false;
+    loop {
+        match next_placeholder(pattern, from) {
+            Err(message) => return Some(message),
+            Ok(None) => return None,
+            Ok(Some((start, end))) => {
+                if after_placeholder && start == from + 1 {
+                    return Some("placeholders in a pattern must be separated by text");
+                }
+                (from, after_placeholder) = (end + 1, true);
+            }
+        }
+    }
+}
+
+const fn has_placeholder(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let mut from = 0;
+    while let Ok(Some(range)) = next_placeholder(pattern, from) {
+        if bytes_eq(name_at(pattern, range), name.as_bytes()) {
+            return true;
+        }
+        from = range.1 + 1;
+    }
+    false
+}
+
+/// What's wrong with `pattern` as the pattern of a [`scan!`](crate::scan!) struct with
+/// `fields`, which must match its placeholders one to one.
+pub const fn pattern_error(pattern: &str, fields: &[&str]) -> Option<&'static str> {
+    if let Some(message) = pattern_shape_error(pattern) {
+        return Some(message);
+    }
+    let bytes = pattern.as_bytes();
+    let mut from = 0;
+    while let Ok(Some(range)) = next_placeholder(bytes, from) {
+        let mut i = 0;
+        while i < fields.len() && !bytes_eq(fields[i].as_bytes(), name_at(bytes, range)) {
+            i += 1;
+        }
+        if i == fields.len() {
+            return Some("pattern has a placeholder that isn't a field");
+        }
+        from = range.1 + 1;
+    }
+    let mut i = 0;
+    while i < fields.len() {
+        if !has_placeholder(pattern, fields[i]) {
+            return Some("pattern is missing a placeholder for a field");
+        }
+        i += 1;
+    }
+    None
+}
+
+fn unescape(literal: &str) -> String {
+    literal.replace("{{", "{").replace("}}", "}")
+}
+
+/// Values captured by the `{name}` placeholders of a pattern, see [`scan!`](crate::scan!).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Captures<'a> {
+    values: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Captures<'a> {
+    /// Matches `input` against `pattern`, where every placeholder takes the shortest text
+    /// up to the literal following it, or the rest of the input for a trailing one.
+    /// `{{` and `}}` stand for literal braces.
+    pub fn match_pattern(pattern: &'a str, input: &'a str) -> Result<Self, ParseError> {
+        if let Some(message) = pattern_shape_error(pattern) {
+            return Err(ParseError::new(pattern, message));
+        }
+        let mut placeholders = Vec::new();
+        let mut from = 0;
+        while let Ok(Some((start, end))) = next_placeholder(pattern.as_bytes(), from) {
+            placeholders.push((unescape(&pattern[from..start - 1]), &pattern[start..end]));
+            from = end + 1;
+        }
+        let trailing = unescape(&pattern[from..]);
+        let expect = |rest: &'a str, literal: &str| {
+            rest.strip_prefix(literal)
+                .ok_or_else(|| ParseError::new(rest, format!("expected {:?}", literal)))
+        };
+        let mut values = Vec::new();
+        let mut rest = input;
+        for (i, (literal, name)) in placeholders.iter().enumerate() {
+            rest = expect(rest, literal)?;
+            let next = placeholders.get(i + 1).map_or(&trailing, |(x, _)| x);
+            let end = match next.is_empty() {
+                true => rest.len(),
+                false => rest
+                    .find(next.as_str())
+                    .ok_or_else(|| ParseError::new(rest, format!("expected {:?}", next)))?,
+            };
+            values.push((*name, &rest[..end]));
+            rest = &rest[end..];
+        }
+        match expect(rest, &trailing)? {
+            "" => Ok(Self { values }),
+            extra => Err(ParseError::new(extra, "unexpected trailing text")),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Result<&'a str, ParseError> {
+        self.values
+            .iter()
+            .find(|(x, _)| *x == name)
+            .map(|(_, value)| *value)
+            .ok_or_else(|| ParseError::new(name, "no such placeholder in the pattern"))
+    }
+
+    pub fn parse<T>(&self, name: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.get(name)?;
+        value
+            .parse()
+            .map_err(|e: T::Err| ParseError::new(value, format!("invalid {}: {}", name, e)))
+    }
+}
+
+/// Declares a struct together with a `FromStr` impl that reads it from text shaped like
+/// `pattern`. Each `{field}` is parsed with the field's own `FromStr`, or with the
+/// `fn(&str) -> Result<T, ParseError>` given after `=>`, and `{{`/`}}` match literal braces:
+///
+/// ```
+/// use advent_of_code::input::ParseError;
+///
+/// fn words(text: &str) -> Result<Vec<String>, ParseError> {
+///     Ok(text.split(' ').map(str::to_string).collect())
+/// }
+///
+/// advent_of_code::scan! {
+///     #[derive(Debug)]
+///     struct Game = "Game {id}: {{{tags}}}" { id: u32, tags: Vec<String> => words }
+/// }
+/// let game = "Game 7: {red blue}".parse::<Game>().unwrap();
+/// assert_eq!((game.id, game.tags.len()), (7, 2));
+/// ```
+///
+/// Placeholders and fields have to match up, which is checked while compiling:
+///
+/// ```compile_fail
+/// advent_of_code::scan! {
+///     struct Game = "Game {id}: {rest}" { id: u32, red: u32 }
+/// }
+/// ```
+#[macro_export]
+macro_rules! scan {
+    (@field $captures:ident, $field:ident) => {
+        $captures.parse(stringify!($field))?
+    };
+    (@field $captures:ident, $field:ident, $parser:expr) => {
+        ($parser)($captures.get(stringify!($field))?)?
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident = $pattern:literal {
+            $($field_vis:vis $field:ident: $ty:ty $(=> $parser:expr)?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($field_vis $field: $ty),*
+        }
+
+        const _: () = {
+            if let Some(message) = $crate::scan::pattern_error($pattern, &[$(stringify!($field)),*]) {
+                panic!("{}", message);
+            }
+        };
+
+        impl ::std::str::FromStr for $name {
+            type Err = $crate::input::ParseError;
+
+            fn from_str(s: &str) -> ::std::result::Result<Self, Self::Err> {
+                let captures = $crate::scan::Captures::match_pattern($pattern, s)?;
+                Ok(Self {
+                    $($field: $crate::scan!(@field captures, $field $(, $parser)?)),*
+                })
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::scan! {
+        #[derive(Debug, PartialEq)]
+        struct Instruction = "{direction} {distance} (#{colour})" {
+            direction: char,
+            distance: i64,
+            colour: String,
+        }
+    }
+
+    #[test]
+    fn test_match_pattern() {
+        let captures = Captures::match_pattern("Game {id}: {rest}", "Game 12: a: b").unwrap();
+        assert_eq!(captures.get("id"), Ok("12"));
+        assert_eq!(captures.get("rest"), Ok("a: b"));
+        assert!(captures.get("other").is_err());
+        assert_eq!(
+            Captures::match_pattern("Game {id}:", "Round 1:")
+                .unwrap_err()
+                .to_string(),
+            "expected \"Game \" in \"Round 1:\""
+        );
+        let part = Captures::match_pattern("{{x={x},m={m}}}", "{x=787,m=2655}").unwrap();
+        assert_eq!((part.get("x"), part.get("m")), (Ok("787"), Ok("2655")));
+    }
+
+    #[test]
+    fn test_bad_patterns() {
+        for (pattern, reason) in [
+            ("{a", "unclosed placeholder in pattern"),
+            ("a}", "unmatched } in pattern, write }} for a literal one"),
+            ("{}", "empty placeholder in pattern"),
+            (
+                "{a}{b}",
+                "placeholders in a pattern must be separated by text",
+            ),
+        ] {
+            assert_eq!(
+                Captures::match_pattern(pattern, "").unwrap_err().reason(),
+                reason
+            );
+        }
+        assert_eq!(pattern_error("{a} {b}", &["a", "b"]), None);
+        assert!(pattern_error("{a} {b}", &["a"]).is_some());
+        assert!(pattern_error("{a}", &["a", "b"]).is_some());
+    }
+
+    #[test]
+    fn test_scan() {
+        assert_eq!(
+            "R 6 (#70c710)".parse::<Instruction>(),
+            Ok(Instruction {
+                direction: 'R',
+                distance: 6,
+                colour: "70c710".to_string()
+            })
+        );
+        let error = "R six (#70c710)".parse::<Instruction>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid distance: invalid digit found in string in \"six\""
+        );
+        assert!("R 6 (#70c710) trailing".parse::<Instruction>().is_err());
+    }
+}
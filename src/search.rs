@@ -0,0 +1,483 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+fn reconstruct_path<S: Clone>(states: &[S], parents: &[usize], mut index: usize) -> Vec<S> {
+    let mut path = vec![states[index].clone()];
+    while parents[index] != index {
+        index = parents[index];
+        path.push(states[index].clone());
+    }
+    path.reverse();
+    path
+}
+
+/// A* search from `start` to the first state satisfying `is_goal`. `heuristic` must never
+/// overestimate the remaining cost. Returns the path including both ends and its cost.
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut states = vec![start.clone()];
+    let mut parents = vec![0];
+    let mut costs = vec![C::default()];
+    let mut index = HashMap::from([(start.clone(), 0)]);
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    while let Some(Reverse((_, cost, current))) = queue.pop() {
+        if cost > costs[current] {
+            continue;
+        }
+        if is_goal(&states[current]) {
+            return Some((reconstruct_path(&states, &parents, current), cost));
+        }
+        for (next, step_cost) in successors(&states[current]) {
+            let next_cost = cost + step_cost;
+            let next_index = match index.entry(next) {
+                Entry::Occupied(entry) => {
+                    let i = *entry.get();
+                    if costs[i] <= next_cost {
+                        continue;
+                    }
+                    costs[i] = next_cost;
+                    parents[i] = current;
+                    i
+                }
+                Entry::Vacant(entry) => {
+                    let i = states.len();
+                    states.push(entry.key().clone());
+                    entry.insert(i);
+                    parents.push(current);
+                    costs.push(next_cost);
+                    i
+                }
+            };
+            let estimate = next_cost + heuristic(&states[next_index]);
+            queue.push(Reverse((estimate, next_cost, next_index)));
+        }
+    }
+    None
+}
+
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Breadth-first search returning the shortest path (in steps) to the first goal state.
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut nodes = vec![start.clone()];
+    let mut parents = vec![0];
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([0]);
+    while let Some(current) = queue.pop_front() {
+        if is_goal(&nodes[current]) {
+            return Some(reconstruct_path(&nodes, &parents, current));
+        }
+        for next in successors(&nodes[current]) {
+            if seen.insert(next.clone()) {
+                queue.push_back(nodes.len());
+                nodes.push(next);
+                parents.push(current);
+            }
+        }
+    }
+    None
+}
+
+/// Number of steps to every state reachable from `start`.
+pub fn bfs_distances<S, I>(start: S, successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    bfs_distances_within(start, successors, usize::MAX)
+}
+
+/// Like [`bfs_distances`], but stops exploring after `max_distance` steps, which makes it
+/// usable on infinite state spaces.
+pub fn bfs_distances_within<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    max_distance: usize,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((current, distance)) = queue.pop_front() {
+        if distance == max_distance {
+            continue;
+        }
+        for next in successors(&current) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Depth-first search returning some path to the first goal state found.
+pub fn dfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut nodes = vec![start.clone()];
+    let mut parents = vec![0];
+    let mut seen = HashSet::new();
+    let mut stack = vec![0];
+    while let Some(current) = stack.pop() {
+        if !seen.insert(nodes[current].clone()) {
+            continue;
+        }
+        if is_goal(&nodes[current]) {
+            return Some(reconstruct_path(&nodes, &parents, current));
+        }
+        for next in successors(&nodes[current]) {
+            if !seen.contains(&next) {
+                stack.push(nodes.len());
+                nodes.push(next);
+                parents.push(current);
+            }
+        }
+    }
+    None
+}
+
+/// Orders `nodes` so that every node comes before its successors, `None` if there is a cycle.
+/// Successors outside of `nodes` are ignored.
+pub fn topological_sort<S, I>(
+    nodes: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let nodes = nodes.into_iter().collect::<Vec<S>>();
+    let index = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (x.clone(), i))
+        .collect::<HashMap<S, usize>>();
+    let edges = nodes
+        .iter()
+        .map(|x| {
+            successors(x)
+                .into_iter()
+                .filter_map(|y| index.get(&y).copied())
+                .collect::<Vec<usize>>()
+        })
+        .collect::<Vec<Vec<usize>>>();
+    let mut in_degree = vec![0; nodes.len()];
+    for &next in edges.iter().flatten() {
+        in_degree[next] += 1;
+    }
+    let mut queue = (0..nodes.len())
+        .filter(|&i| in_degree[i] == 0)
+        .collect::<VecDeque<usize>>();
+    let mut order = Vec::with_capacity(nodes.len());
+    while let Some(current) = queue.pop_front() {
+        order.push(nodes[current].clone());
+        for &next in &edges[current] {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                queue.push_back(next);
+            }
+        }
+    }
+    (order.len() == nodes.len()).then_some(order)
+}
+
+/// Groups of nodes connected to each other, treating `successors` as undirected edges, so an
+/// edge only listed by one of its ends still joins both.
+pub fn connected_components<S, I>(
+    nodes: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+) -> Vec<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let (all, mut edges) = index_graph(nodes, successors);
+    for from in 0..edges.len() {
+        for i in 0..edges[from].len() {
+            let to = edges[from][i];
+            edges[to].push(from);
+        }
+    }
+    let mut seen = vec![false; all.len()];
+    let mut components = Vec::new();
+    for root in 0..all.len() {
+        if seen[root] {
+            continue;
+        }
+        seen[root] = true;
+        let mut component = Vec::new();
+        let mut stack = vec![root];
+        while let Some(current) = stack.pop() {
+            for &next in &edges[current] {
+                if !seen[next] {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+            component.push(all[current].clone());
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// Interns every node reachable from `nodes`, returning them with index-based adjacency lists.
+fn index_graph<S, I>(
+    nodes: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+) -> (Vec<S>, Vec<Vec<usize>>)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut all = Vec::new();
+    let mut index = HashMap::new();
+    for node in nodes {
+        if let Entry::Vacant(entry) = index.entry(node.clone()) {
+            entry.insert(all.len());
+            all.push(node);
+        }
+    }
+    let mut edges = Vec::new();
+    while edges.len() < all.len() {
+        let next = successors(&all[edges.len()])
+            .into_iter()
+            .map(|x| match index.entry(x) {
+                Entry::Occupied(entry) => *entry.get(),
+                Entry::Vacant(entry) => {
+                    all.push(entry.key().clone());
+                    *entry.insert(all.len() - 1)
+                }
+            })
+            .collect();
+        edges.push(next);
+    }
+    (all, edges)
+}
+
+/// Tarjan's algorithm. Components come out in reverse topological order, i.e. a component
+/// is listed before any component that has an edge into it.
+pub fn strongly_connected_components<S, I>(
+    nodes: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+) -> Vec<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    const UNVISITED: usize = usize::MAX;
+    let (all, edges) = index_graph(nodes, successors);
+    let mut order = vec![UNVISITED; all.len()];
+    let mut low_link = vec![UNVISITED; all.len()];
+    let mut on_stack = vec![false; all.len()];
+    let mut stack = Vec::new();
+    let mut counter = 0;
+    let mut components = Vec::new();
+    for root in 0..all.len() {
+        if order[root] != UNVISITED {
+            continue;
+        }
+        let mut work = vec![(root, 0)];
+        while let Some(&mut (v, ref mut i)) = work.last_mut() {
+            if *i == 0 {
+                order[v] = counter;
+                low_link[v] = counter;
+                counter += 1;
+                stack.push(v);
+                on_stack[v] = true;
+            }
+            if let Some(&w) = edges[v].get(*i) {
+                *i += 1;
+                if order[w] == UNVISITED {
+                    work.push((w, 0));
+                } else if on_stack[w] {
+                    low_link[v] = low_link[v].min(order[w]);
+                }
+                continue;
+            }
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                low_link[parent] = low_link[parent].min(low_link[v]);
+            }
+            if low_link[v] == order[v] {
+                let mut component = Vec::new();
+                loop {
+                    let w = stack.pop().unwrap();
+                    on_stack[w] = false;
+                    component.push(all[w].clone());
+                    if w == v {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges(node: &u8) -> Vec<(u8, u32)> {
+        match node {
+            0 => vec![(1, 7), (2, 9), (5, 14)],
+            1 => vec![(0, 7), (2, 10), (3, 15)],
+            2 => vec![(0, 9), (1, 10), (3, 11), (5, 2)],
+            3 => vec![(1, 15), (2, 11), (4, 6)],
+            4 => vec![(3, 6), (5, 9)],
+            5 => vec![(0, 14), (2, 2), (4, 9)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(
+            dijkstra(0, edges, |&x| x == 4),
+            Some((vec![0, 2, 5, 4], 20))
+        );
+        assert_eq!(dijkstra(0, edges, |&x| x == 0), Some((vec![0], 0)));
+        assert_eq!(dijkstra(0, edges, |&x| x == 6), None);
+    }
+
+    #[test]
+    fn test_astar_grid() {
+        let goal = (4i32, 3i32);
+        let walls = [(1, 0), (1, 1), (1, 2), (3, 1), (3, 2), (3, 3)];
+        let (path, cost) = astar(
+            (0, 0),
+            |&(x, y): &(i32, i32)| {
+                [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                    .into_iter()
+                    .filter(|p| (0..5).contains(&p.0) && (0..4).contains(&p.1))
+                    .filter(|p| !walls.contains(p))
+                    .map(|p| (p, 1u32))
+                    .collect::<Vec<_>>()
+            },
+            |&(x, y)| x.abs_diff(goal.0) + y.abs_diff(goal.1),
+            |&p| p == goal,
+        )
+        .unwrap();
+        assert_eq!(cost, 13);
+        assert_eq!(path.len(), 14);
+    }
+
+    fn directed(node: &char) -> Vec<char> {
+        match node {
+            'a' => vec!['b'],
+            'b' => vec!['c', 'e', 'f'],
+            'c' => vec!['d', 'g'],
+            'd' => vec!['c', 'h'],
+            'e' => vec!['a', 'f'],
+            'f' => vec!['g'],
+            'g' => vec!['f'],
+            'h' => vec!['d', 'g'],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        assert_eq!(
+            bfs('a', directed, |&x| x == 'g'),
+            Some(vec!['a', 'b', 'c', 'g'])
+        );
+        assert_eq!(bfs('f', directed, |&x| x == 'a'), None);
+        let distances = bfs_distances('a', directed);
+        assert_eq!(distances.len(), 8);
+        assert_eq!(distances[&'h'], 4);
+        let limited = bfs_distances_within(0i64, |&x| [x - 1, x + 1], 3);
+        assert_eq!(limited.len(), 7);
+    }
+
+    #[test]
+    fn test_dfs() {
+        let path = dfs('a', directed, |&x| x == 'h').unwrap();
+        assert_eq!(path.first(), Some(&'a'));
+        assert_eq!(path.last(), Some(&'h'));
+        assert!(path.windows(2).all(|w| directed(&w[0]).contains(&w[1])));
+        assert_eq!(dfs('g', directed, |&x| x == 'a'), None);
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let order =
+            topological_sort(1..=6u32, |&x| (x + 1..=6).filter(move |y| y % x == 0)).unwrap();
+        assert_eq!(order, vec![1, 2, 3, 5, 4, 6]);
+        assert_eq!(topological_sort("abcdefgh".chars(), directed), None);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let components = connected_components(0..10u32, |&x| match x {
+            9 => vec![],
+            x => vec![(x + 3) % 9, (x + 6) % 9],
+        });
+        assert_eq!(components.len(), 4);
+        assert_eq!(
+            components.iter().map(Vec::len).collect::<Vec<usize>>(),
+            vec![3, 3, 3, 1]
+        );
+        let mut one_way = connected_components([1u32, 0], |&x| match x {
+            0 => vec![1],
+            _ => vec![],
+        });
+        one_way[0].sort();
+        assert_eq!(one_way, vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let mut components = strongly_connected_components(['a'], directed);
+        for component in components.iter_mut() {
+            component.sort();
+        }
+        assert_eq!(
+            components,
+            vec![vec!['f', 'g'], vec!['c', 'd', 'h'], vec!['a', 'b', 'e']]
+        );
+    }
+}
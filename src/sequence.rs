@@ -0,0 +1,113 @@
+use crate::math::SignedInt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Forward,
+    Backward,
+}
+
+/// Repeated differences of a sequence, down to the first all-zero row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifferenceTable<T> {
+    rows: Vec<Vec<T>>,
+}
+
+impl<T: SignedInt> DifferenceTable<T> {
+    pub fn new(values: Vec<T>) -> Self {
+        let mut rows = vec![values];
+        loop {
+            let last = rows.last().unwrap();
+            if last.len() < 2 || last.iter().all(|&x| x == T::ZERO) {
+                break;
+            }
+            let next = last.windows(2).map(|w| w[1] - w[0]).collect();
+            rows.push(next);
+        }
+        Self { rows }
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.rows[0]
+    }
+
+    pub fn rows(&self) -> &[Vec<T>] {
+        &self.rows
+    }
+
+    pub fn extrapolate(&self, direction: Direction) -> T {
+        match direction {
+            Direction::Forward => self
+                .rows
+                .iter()
+                .rev()
+                .filter_map(|row| row.last())
+                .fold(T::ZERO, |acc, &x| acc + x),
+            Direction::Backward => self
+                .rows
+                .iter()
+                .rev()
+                .filter_map(|row| row.first())
+                .fold(T::ZERO, |acc, &x| x - acc),
+        }
+    }
+
+    /// Coefficients of the Newton forward-difference form, i.e. the first entry of every row.
+    pub fn newton_coefficients(&self) -> Vec<T> {
+        self.rows
+            .iter()
+            .filter_map(|row| row.first().copied())
+            .collect()
+    }
+
+    /// Evaluates the interpolating polynomial at index `x` (0 is the first value),
+    /// `x` may lie outside of the known values in either direction.
+    pub fn value_at(&self, x: T) -> T {
+        let mut result = T::ZERO;
+        let mut binomial = T::ONE;
+        let mut k = T::ZERO;
+        for coefficient in self.newton_coefficients() {
+            result = result + binomial * coefficient;
+            binomial = binomial * (x - k) / (k + T::ONE);
+            k = k + T::ONE;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difference_rows() {
+        let table = DifferenceTable::new(vec![1, 3, 6, 10, 15, 21]);
+        assert_eq!(
+            table.rows(),
+            &[
+                vec![1, 3, 6, 10, 15, 21],
+                vec![2, 3, 4, 5, 6],
+                vec![1, 1, 1, 1],
+                vec![0, 0, 0]
+            ]
+        );
+        assert_eq!(table.newton_coefficients(), vec![1, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let table = DifferenceTable::new(vec![10i64, 13, 16, 21, 30, 45]);
+        assert_eq!(table.extrapolate(Direction::Forward), 68);
+        assert_eq!(table.extrapolate(Direction::Backward), 5);
+    }
+
+    #[test]
+    fn test_value_at() {
+        let table = DifferenceTable::new(vec![10i64, 13, 16, 21, 30, 45]);
+        assert_eq!(table.value_at(6), 68);
+        assert_eq!(table.value_at(-1), 5);
+        assert_eq!(table.value_at(2), 16);
+        let squares = DifferenceTable::new(vec![0i32, 1, 4]);
+        assert_eq!(squares.value_at(10), 100);
+        assert_eq!(squares.value_at(-7), 49);
+    }
+}
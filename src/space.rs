@@ -0,0 +1,119 @@
+use std::ops::{Add, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan_distance(self, other: Point3) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs()
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+/// Axis-aligned box covering every point between `min` and `max`, both inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Cuboid {
+    /// Box spanning two opposite corners given in any order.
+    pub fn new(a: Point3, b: Point3) -> Self {
+        Self {
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    pub fn volume(&self) -> i64 {
+        let size = self.max - self.min;
+        (size.x + 1) * (size.y + 1) * (size.z + 1)
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    pub fn intersects(&self, other: &Cuboid) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+            && self.min.z <= other.max.z
+            && other.min.z <= self.max.z
+    }
+
+    pub fn translate(&self, offset: Point3) -> Cuboid {
+        Cuboid {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    /// `(x, y)` columns the cuboid covers when looked at from above.
+    pub fn footprint(&self) -> impl Iterator<Item = (i64, i64)> {
+        let (min, max) = (self.min, self.max);
+        (min.x..=max.x).flat_map(move |x| (min.y..=max.y).map(move |y| (x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cuboid() {
+        let cuboid = Cuboid::new(Point3::new(2, 0, 5), Point3::new(0, 0, 4));
+        assert_eq!(cuboid.min, Point3::new(0, 0, 4));
+        assert_eq!(cuboid.volume(), 6);
+        assert!(cuboid.contains(Point3::new(1, 0, 5)));
+        assert!(!cuboid.contains(Point3::new(1, 1, 5)));
+        assert_eq!(cuboid.footprint().count(), 3);
+    }
+
+    #[test]
+    fn test_intersects() {
+        let a = Cuboid::new(Point3::new(0, 0, 0), Point3::new(2, 2, 2));
+        let b = Cuboid::new(Point3::new(2, 2, 2), Point3::new(3, 3, 3));
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&b.translate(Point3::new(0, 0, 1))));
+        assert_eq!(
+            Point3::new(1, 2, 3).manhattan_distance(Point3::default()),
+            6
+        );
+    }
+}